@@ -0,0 +1,186 @@
+// Standard Uses
+use std::path::Path;
+
+// Crate Uses
+use crate::attribute;
+
+// External Uses
+use ungrammar_extra::{SyntaxKindMeta, TokenCategory};
+use ungrammar_gen::generator::{self, GeneratorOptions, KindsMeta};
+use virtue::prelude::*;
+
+
+/// Name of the macro the `SyntaxKind` derive emits for the token kind enum, `ast!` calls it
+/// since it's the only way to get the kinds information of the enum at expansion time
+pub fn callback_name(token_kind: &str) -> String {
+    format!("__ungrammar_ast_{token_kind}")
+}
+
+/// `ast!("grammar.ungram", path::to::TokenKind)` expands into a call to the macro emitted
+/// by the derive next to the enum, which then calls [`ast_with_kinds`] with the kinds
+pub fn ast(input: TokenStream) -> Result<TokenStream> {
+    let mut tokens = input.into_iter();
+
+    let grammar = match tokens.next() {
+        Some(TokenTree::Literal(lit)) => lit,
+        token => return Err(expected("the grammar path as a string literal", token)),
+    };
+    separator(tokens.next(), "the token kind enum")?;
+
+    let mut path: Vec<TokenTree> = tokens.collect();
+    let Some(TokenTree::Ident(token_kind)) = path.last().cloned() else {
+        return Err(expected("the path of the token kind enum", path.last().cloned()));
+    };
+
+    // The derive re-exports the macro next to the enum, so it's reachable from the same path
+    let callback = Ident::new(&callback_name(&token_kind.to_string()), token_kind.span());
+    let path_stream: TokenStream = path.iter().cloned().collect();
+    *path.last_mut().unwrap() = TokenTree::Ident(callback);
+
+    let mut output: TokenStream = path.into_iter().collect();
+    output.extend([
+        TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+        TokenTree::Group(Group::new(Delimiter::Parenthesis, {
+            let mut args = TokenStream::from(TokenTree::Literal(grammar));
+            args.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+            args.extend(path_stream);
+            args
+        })),
+        TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+    ]);
+
+    Ok(output)
+}
+
+/// Expands the grammar into the AST, the input is `"grammar.ungram", [(lit, Name, desc, tags...), ...], path`
+pub fn ast_with_kinds(input: TokenStream) -> Result<TokenStream> {
+    let mut tokens = input.into_iter();
+
+    let grammar = match tokens.next() {
+        Some(TokenTree::Literal(lit)) => lit,
+        token => return Err(expected("the grammar path as a string literal", token)),
+    };
+    let span = grammar.span();
+    let grammar = string_value(&grammar).ok_or_else(|| Error::custom_at("Expected a string literal", span))?;
+
+    separator(tokens.next(), "the token kinds list")?;
+    let kinds = match tokens.next() {
+        Some(TokenTree::Group(kinds)) if kinds.delimiter() == Delimiter::Bracket => kinds,
+        token => return Err(expected("the token kinds list", token)),
+    };
+    let kinds = parse_kinds(kinds.stream())?;
+
+    separator(tokens.next(), "the token kind enum")?;
+    let path: String = tokens.map(|t| t.to_string()).collect();
+    if path.is_empty() {
+        return Err(Error::custom_at("Expected the path of the token kind enum", span));
+    }
+    // The kind module is nested in the expansion, so relative paths need to go up once more
+    let token_kind = match path.starts_with("crate") || path.starts_with("::") {
+        true => path,
+        false => format!("super::{path}"),
+    };
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let grammar_path = Path::new(&manifest_dir).join(grammar);
+
+    let source = generator::to_source_with_meta(
+        &grammar_path, &KindsMeta::from_kinds(kinds), &token_kind, &GeneratorOptions::default()
+    ).map_err(|e| Error::custom_at(e.to_string(), span))?;
+
+    // Including the grammar makes the compiler track it, so the AST is expanded again when it changes
+    let mut output: TokenStream = format!(
+        "const _: &str = include_str!({:?});", grammar_path.display().to_string()
+    ).parse().unwrap();
+    output.extend(source.parse::<TokenStream>().map_err(|e| Error::custom_at(e.to_string(), span))?);
+
+    Ok(output)
+}
+
+fn parse_kinds(stream: TokenStream) -> Result<Vec<SyntaxKindMeta>> {
+    let mut kinds = vec![];
+
+    for token in stream {
+        let TokenTree::Group(kind) = token else { continue };
+        let parts: Vec<TokenTree> = kind.stream().into_iter()
+            .filter(|t| !matches!(t, TokenTree::Punct(p) if p.as_char() == ','))
+            .collect();
+
+        let [TokenTree::Literal(literal), TokenTree::Ident(name), TokenTree::Literal(description), tags @ ..] = &parts[..]
+        else {
+            return Err(Error::custom_at("Expected a `(literal, Name, description, tags...)` kind", kind.span()));
+        };
+        let tagged = |tag: &str| tags.iter().any(|t| matches!(t, TokenTree::Ident(i) if i.to_string() == tag));
+        let value = |lit: &Literal| string_value(lit).ok_or_else(|| {
+            Error::custom_at("Expected a string literal", lit.span())
+        });
+
+        kinds.push(SyntaxKindMeta {
+            literal: value(literal)?,
+            name: name.to_string(),
+            description: value(description)?,
+            trivia: tagged("trivia"),
+            error: tagged("error"),
+            category: tags.iter().find_map(|t| match t {
+                TokenTree::Ident(i) => TokenCategory::from_name(&i.to_string()),
+                _ => None,
+            }),
+            operators: tags
+                .windows(2)
+                .filter_map(|pair| match pair {
+                    [TokenTree::Ident(key), TokenTree::Group(list)] => Some(attribute::parse_operator(key, list)),
+                    _ => None,
+                })
+                .collect::<Result<_>>()?,
+        });
+    }
+
+    Ok(kinds)
+}
+
+/// Value of a string literal token, `None` if the literal is not a string
+fn string_value(lit: &Literal) -> Option<String> {
+    let text = lit.to_string();
+
+    if let Some(raw) = text.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        return Some(raw.get(hashes + 1..raw.len() - hashes - 1)?.to_owned());
+    }
+
+    let mut chars = text.strip_prefix('"')?.strip_suffix('"')?.chars();
+    let mut value = String::new();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+
+        match chars.next()? {
+            'n' => value.push('\n'),
+            'r' => value.push('\r'),
+            't' => value.push('\t'),
+            '0' => value.push('\0'),
+            'x' => value.push(u8::from_str_radix(&chars.by_ref().take(2).collect::<String>(), 16).ok()? as char),
+            'u' => {
+                let code: String = chars.by_ref().skip(1).take_while(|c| *c != '}').collect();
+                value.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+            },
+            '\n' => while chars.as_str().starts_with(char::is_whitespace) { chars.next(); },
+            c => value.push(c),
+        }
+    }
+
+    Some(value)
+}
+
+/// Checks the `,` in between two arguments, `next` is what comes after it
+fn separator(token: Option<TokenTree>, next: &str) -> Result<()> {
+    match token {
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => Ok(()),
+        token => Err(expected(&format!("`,` followed by {next}"), token)),
+    }
+}
+
+fn expected(what: &str, token: Option<TokenTree>) -> Error {
+    Error::custom_at_opt_token(format!("Expected {what}"), token)
+}
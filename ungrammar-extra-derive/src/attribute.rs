@@ -1,46 +1,90 @@
 // Standard Uses
+use std::str::FromStr;
 
 // Crate Uses
 
 // External Uses
+use ungrammar_extra::{Assoc, Fixity, Operator, TokenCategory};
 use virtue::parse::Attribute;
 use virtue::prelude::*;
-use virtue::utils::{ParsedAttribute, parse_tagged_attribute};
 
 pub struct TokenInfo {
     pub literal: String,
     pub description: Option<String>,
     pub variant_name: String,
+    pub trivia: bool,
+    pub error: bool,
+    pub category: Option<TokenCategory>,
+    pub operators: Vec<Operator>,
 }
 
-pub fn parse_tokens_info_attribute(
-    attrs: &[Attribute],
-    variant_name: String,
-) -> Result<Option<TokenInfo>> {
+/// An item of `#[syntax(...)]`, like virtue's `ParsedAttribute` but it also takes lists (ex: `infix(...)`)
+pub enum SyntaxItem {
+    Tag(Ident),
+    Property(Ident, Literal),
+    List(Ident, Group),
+}
+
+/// Parses the `#[syntax(...)]` attributes of a variant, errors point at the offending key or variant
+pub fn parse_tokens_info_attribute(attrs: &[Attribute], variant: &Ident) -> Result<TokenInfo> {
+    let variant_name = variant.to_string();
     let (mut lit, mut desc) = (None, None);
+    let (mut trivia, mut error, mut category) = (false, false, None);
+    let mut operators = vec![];
 
     for attr in attrs {
-        let Some(attributes) = parse_tagged_attribute(&attr.tokens, "syntax")? else {
+        let Some(attributes) = parse_syntax_attribute(&attr.tokens)? else {
             continue;
         };
 
-        for attr in attributes {
-            match attr {
-                ParsedAttribute::Property(key, val) => match &*key.to_string() {
+        for parsed in attributes {
+            match parsed {
+                SyntaxItem::Property(key, val) => match &*key.to_string() {
                     "lit" => lit = Some(val.to_string()),
                     "desc" => desc = Some(val.to_string()),
-                    k => panic!("Unexpected key: {:?}", k),
+                    "category" => {
+                        let name = val.to_string();
+                        category = Some(TokenCategory::from_name(name.trim_matches('"')).ok_or_else(|| {
+                            Error::custom_at(
+                                format!(
+                                    "Unknown category {name}, expected one of {}",
+                                    TokenCategory::ALL.map(|c| format!("\"{}\"", c.name())).join(", ")
+                                ),
+                                val.span(),
+                            )
+                        })?);
+                    }
+                    k => {
+                        return Err(Error::custom_at(
+                            format!("Unknown key `{k}`, expected `lit`, `desc` or `category`"),
+                            key.span(),
+                        ))
+                    }
                 },
-                x => panic!("Unexpected attribute: {:?}", x),
+                SyntaxItem::Tag(tag) => match &*tag.to_string() {
+                    "trivia" => trivia = true,
+                    "error" => error = true,
+                    t => {
+                        return Err(Error::custom_at(
+                            format!("Unknown attribute `{t}`, expected `trivia` or `error`"),
+                            tag.span(),
+                        ))
+                    }
+                },
+                SyntaxItem::List(key, list) => operators.push(parse_operator(&key, &list)?),
             }
         }
     }
 
     if lit.is_none() {
         if !variant_name.is_ascii() {
-            panic!(
-                "Literal contains numbers, it can only be alphabetic(A to Z letters) at variant'{variant_name}'."
-            )
+            return Err(Error::custom_at(
+                format!(
+                    "Variant '{variant_name}' has no literal and its name can't be used as one, \
+                    since it can only be alphabetic (A to Z letters)"
+                ),
+                variant.span(),
+            ));
         }
         
         let mut s = String::with_capacity(variant_name.len() + 2);
@@ -50,22 +94,177 @@ pub fn parse_tokens_info_attribute(
 
         //lit = Some(backtick(&*s));
         lit = Some(s);
-
-
-        /*
-        panic!(
-            "Literal was not specified at variant '{variant_name}'.\n\
-            Try doing for example:\n\
-            #[syntax(lit=\"...\", ...)]\n{variant_name}"
-        )
-        */
     }
 
-    Ok(Some(TokenInfo {
+    Ok(TokenInfo {
         literal: lit.unwrap(),
         description: desc,
         variant_name,
-    }))
+        trivia,
+        error,
+        category,
+        operators,
+    })
+}
+
+/// Name of the `T![...]` macro if the enum asks for it with `#[syntax(token_macro)]`, or
+/// `#[syntax(token_macro = "Tok")]` to name it something else than `T`
+pub fn parse_token_macro_attribute(attrs: &[Attribute]) -> Result<Option<String>> {
+    let mut name = None;
+
+    for attr in attrs {
+        let Some(attributes) = parse_syntax_attribute(&attr.tokens)? else {
+            continue;
+        };
+
+        for parsed in attributes {
+            match parsed {
+                SyntaxItem::Tag(key) if key.to_string() == "token_macro" => name = Some("T".to_owned()),
+                SyntaxItem::Property(key, value) if key.to_string() == "token_macro" => {
+                    let literal = value.to_string();
+                    let macro_name = literal.trim_matches('"');
+                    let is_ident = matches!(
+                        TokenStream::from_str(macro_name).map(|s| s.into_iter().collect::<Vec<_>>()).as_deref(),
+                        Ok([TokenTree::Ident(_)])
+                    );
+                    if !is_ident || !literal.starts_with('"') {
+                        return Err(Error::custom_at(
+                            format!("Expected the name of the macro (ex: \"T\"), got {literal}"),
+                            value.span(),
+                        ));
+                    }
+                    name = Some(macro_name.to_owned());
+                }
+                SyntaxItem::Tag(key) | SyntaxItem::Property(key, _) | SyntaxItem::List(key, _) => {
+                    return Err(Error::custom_at(
+                        format!("Unknown key `{key}`, expected `token_macro` or `token_macro = \"...\"`"),
+                        key.span(),
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(name)
+}
+
+/// Items of the attribute if it's a `syntax(...)` one
+fn parse_syntax_attribute(group: &Group) -> Result<Option<Vec<SyntaxItem>>> {
+    let mut stream = group.stream().into_iter();
+
+    match (stream.next(), stream.next()) {
+        (Some(TokenTree::Ident(ident)), Some(TokenTree::Group(items))) if ident.to_string() == "syntax" => {
+            parse_syntax_items(items.stream()).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Parses comma separated `key`, `key = "value"` and `key(...)` items
+pub fn parse_syntax_items(stream: TokenStream) -> Result<Vec<SyntaxItem>> {
+    let mut items = vec![];
+    let mut stream = stream.into_iter();
+
+    while let Some(token) = stream.next() {
+        let TokenTree::Ident(key) = token else {
+            return Err(Error::custom_at("Expected `key`, `key = \"val\"` or `key(...)`", token.span()));
+        };
+
+        match stream.next() {
+            None => {
+                items.push(SyntaxItem::Tag(key));
+                break;
+            }
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {
+                items.push(SyntaxItem::Tag(key));
+                continue;
+            }
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' => match stream.next() {
+                Some(TokenTree::Literal(value)) => items.push(SyntaxItem::Property(key, value)),
+                token => return Err(Error::custom_at_opt_token("Expected a literal", token)),
+            },
+            Some(TokenTree::Group(list)) if list.delimiter() == Delimiter::Parenthesis => {
+                items.push(SyntaxItem::List(key, list))
+            }
+            token => return Err(Error::custom_at_opt_token("Expected `,`, `= \"val\"` or `(...)`", token)),
+        }
+
+        match stream.next() {
+            None => break,
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {}
+            token => return Err(Error::custom_at_opt_token("Expected `,`", token)),
+        }
+    }
+
+    Ok(items)
+}
+
+/// Parses `infix(prec = 10, assoc = "left")`, `prefix(prec = 20)` or `postfix(prec = 30)`,
+/// the associativity is optional and defaults to left
+pub fn parse_operator(key: &Ident, list: &Group) -> Result<Operator> {
+    let (mut prec, mut assoc) = (None, None);
+    let infix = key.to_string() == "infix";
+
+    for item in parse_syntax_items(list.stream())? {
+        match item {
+            SyntaxItem::Property(k, value) if k.to_string() == "prec" => {
+                let parsed = value.to_string().parse::<u8>().ok().filter(|prec| *prec < u8::MAX);
+                prec = Some(parsed.ok_or_else(|| {
+                    Error::custom_at(format!("Precedence must be an integer below {}", u8::MAX), value.span())
+                })?);
+            }
+            SyntaxItem::Property(k, value) if infix && k.to_string() == "assoc" => {
+                let name = value.to_string();
+                assoc = Some(Assoc::from_name(name.trim_matches('"')).ok_or_else(|| {
+                    Error::custom_at(
+                        format!("Unknown associativity {name}, expected \"left\" or \"right\""),
+                        value.span(),
+                    )
+                })?);
+            }
+            SyntaxItem::Tag(k) | SyntaxItem::Property(k, _) | SyntaxItem::List(k, _) => {
+                let expected = if infix { "`prec` or `assoc`" } else { "`prec`" };
+                return Err(Error::custom_at(format!("Unknown key `{k}`, expected {expected}"), k.span()));
+            }
+        }
+    }
+
+    let fixity = match &*key.to_string() {
+        "infix" => Fixity::Infix(assoc.unwrap_or(Assoc::Left)),
+        "prefix" => Fixity::Prefix,
+        "postfix" => Fixity::Postfix,
+        k => {
+            return Err(Error::custom_at(
+                format!("Unknown attribute `{k}`, expected `infix`, `prefix` or `postfix`"),
+                key.span(),
+            ))
+        }
+    };
+    let prec = prec.ok_or_else(|| Error::custom_at("Missing the precedence, `prec = ...`", list.span()))?;
+
+    Ok(Operator::new(fixity, prec))
+}
+
+/// The operator as written in the attribute, to be parsed back by [`parse_operator`]
+pub fn operator_attribute(operator: &Operator) -> String {
+    match operator.fixity {
+        Fixity::Infix(assoc) => {
+            let assoc = if assoc == Assoc::Left { "left" } else { "right" };
+            format!("infix(prec = {}, assoc = \"{assoc}\")", operator.prec())
+        }
+        Fixity::Prefix => format!("prefix(prec = {})", operator.prec()),
+        Fixity::Postfix => format!("postfix(prec = {})", operator.prec()),
+    }
+}
+
+/// The operator as an expression of its `ungrammar_extra` type
+pub fn operator_expression(operator: &Operator) -> String {
+    let fixity = match operator.fixity {
+        Fixity::Infix(assoc) => format!("Infix(ungrammar_extra::Assoc::{assoc:?})"),
+        fixity => format!("{fixity:?}"),
+    };
+
+    format!("ungrammar_extra::Operator::new(ungrammar_extra::Fixity::{fixity}, {})", operator.prec())
 }
 
 /*
@@ -1,41 +1,52 @@
 // Standard Uses
+use std::str::FromStr;
 
 // Crate Uses
 use crate::attribute::{self, TokenInfo};
 
 // External Uses
+use virtue::parse::Generics;
 use virtue::prelude::*;
 
 pub(crate) struct DeriveEnum {
     pub variants: Vec<EnumVariant>,
+    pub generics: Option<Generics>,
 }
 
 impl DeriveEnum {
-    pub fn new(variants: Vec<EnumVariant>) -> Self {
-        Self { variants }
+    pub fn new(variants: Vec<EnumVariant>, generics: Option<Generics>) -> Self {
+        Self { variants, generics }
     }
-    pub fn generate_encode_new(self, generator: &mut Generator) -> Result<Self> {
+    /// The `kind_generator` targets `SyntaxKind`, which unlike the token kind never has generics
+    pub fn generate_encode_new(
+        self,
+        generator: &mut Generator,
+        kind_generator: &mut Generator,
+    ) -> Result<Self> {
         let mut attrs = vec![];
 
         for variant in &self.variants {
             attrs.push(attribute::parse_tokens_info_attribute(
                 &variant.attributes,
-                variant.name.to_string(),
+                &variant.name,
             )?);
         }
 
         // TODO: This might not be necessary, or may be optional, decide later if to make this
         //       optional for the user to opt-out
         self.generate_syntax_kind_enum(generator)?;
+        self.generate_into_impl(generator, kind_generator)?;
 
-        self.generate_kinds_meta_info_trait_impl(generator, &attrs)?;
+        self.generate_kinds_meta_info_trait_impl(kind_generator, &attrs)?;
 
         Ok(self)
     }
 
     fn generate_syntax_kind_enum(&self, generator: &mut Generator) -> Result<()> {
         let mut kind_enum = generator.generate_enum("SyntaxKind");
-        let kind_enum = kind_enum.make_pub();
+        let kind_enum = kind_enum
+            .make_pub()
+            .with_derives(["Debug", "Clone", "Copy", "PartialEq", "Eq", "Hash"]);
 
         for variant in &self.variants {
             kind_enum.add_value(variant.name.to_string()).make_zst();
@@ -49,7 +60,7 @@ impl DeriveEnum {
     fn generate_kinds_meta_info_trait_impl(
         &self,
         generator: &mut Generator,
-        attrs: &[Option<TokenInfo>],
+        attrs: &[TokenInfo],
     ) -> Result<()> {
         let prefix = "TOKENKIND_";
 
@@ -109,8 +120,7 @@ impl DeriveEnum {
         
         */
         
-        let mut meta_impl =
-        generator.impl_trait_for_other_type("ungrammar_extra::KindsMetaInfo", "SyntaxKind");
+        let mut meta_impl = generator.impl_for("ungrammar_extra::KindsMetaInfo");
 
         meta_impl
             .generate_fn("literals")
@@ -119,14 +129,7 @@ impl DeriveEnum {
                 let mut body = "&[".to_string();
 
                 for attr in attrs {
-                    match attr {
-                        Some(attr) => {
-                            body += &*format!("\t{},\n", attr.literal);
-                        }
-                        None => {
-                            todo!("rust bad err#3")
-                        }
-                    }
+                    body += &*format!("\t{},\n", attr.literal);
                 }
 
                 body += "]";
@@ -135,6 +138,55 @@ impl DeriveEnum {
                 Ok(())
             })?;
 
+        let trivia: Vec<_> = attrs.iter().filter(|a| a.trivia).map(|a| &*a.literal).collect();
+        let errors: Vec<_> = attrs.iter().filter(|a| a.error).map(|a| &*a.literal).collect();
+
+        for (name, literals) in [("trivia", trivia), ("errors", errors)] {
+            if literals.is_empty() { continue }
+
+            meta_impl
+                .generate_fn(name)
+                .with_return_type("&'static [&'static str]")
+                .body(|b| {
+                    b.push_parsed(format!("&[{}]", literals.join(", ")))?;
+                    Ok(())
+                })?;
+        }
+
+        let categories: Vec<_> = attrs
+            .iter()
+            .filter_map(|a| Some(format!("({}, ungrammar_extra::TokenCategory::{:?})", a.literal, a.category?)))
+            .collect();
+        if !categories.is_empty() {
+            meta_impl
+                .generate_fn("categories")
+                .with_return_type("&'static [(&'static str, ungrammar_extra::TokenCategory)]")
+                .body(|b| {
+                    b.push_parsed(format!("&[{}]", categories.join(", ")))?;
+                    Ok(())
+                })?;
+        }
+
+        let operators: Vec<_> = attrs
+            .iter()
+            .flat_map(|a| {
+                a.operators.iter().map(|op| format!("({}, {})", a.literal, attribute::operator_expression(op)))
+            })
+            .collect();
+        if !operators.is_empty() {
+            meta_impl
+                .generate_fn("operators")
+                .with_return_type("&'static [(&'static str, ungrammar_extra::Operator)]")
+                .body(|b| {
+                    // Built in a const, so the checks of `Operator::new` run at compile time
+                    b.push_parsed(format!(
+                        "const OPERATORS: &[(&str, ungrammar_extra::Operator)] = &[{}]; OPERATORS",
+                        operators.join(", ")
+                    ))?;
+                    Ok(())
+                })?;
+        }
+
         meta_impl
             .generate_fn("kinds")
             .with_return_type("&'static std::collections::HashMap<&'static str, &'static str>")
@@ -156,7 +208,7 @@ impl DeriveEnum {
 }
 
 
-pub fn generate_kinds_meta_info(attrs: &[Option<TokenInfo>]) -> Result<String> {
+pub fn generate_kinds_meta_info(attrs: &[TokenInfo]) -> Result<String> {
     let prefix = "TOKENKIND_";
     
     let mut kinds = format!(
@@ -170,21 +222,14 @@ pub fn generate_kinds_meta_info(attrs: &[Option<TokenInfo>]) -> Result<String> {
     );        
 
     for attr in attrs {
-        match attr {
-            Some(attr) => {
-                kinds += &*format!(
-                    "\t({}, \"{}\"), \n", attr.literal, attr.variant_name
-                );
-
-                let desc = attr.description.clone().unwrap_or(
-                    format!("\"{}\"", &attr.variant_name)
-                );
-                descriptions += &*format!("\t({}, {desc}), \n", attr.literal);
-            }
-            None => {
-                todo!("rust bad err#1")
-            }
-        }
+        kinds += &*format!(
+            "\t({}, \"{}\"), \n", attr.literal, attr.variant_name
+        );
+
+        let desc = attr.description.clone().unwrap_or(
+            format!("\"{}\"", attr.variant_name)
+        );
+        descriptions += &*format!("\t({}, {desc}), \n", attr.literal);
     }
 
     kinds += "]));";
@@ -193,3 +238,61 @@ pub fn generate_kinds_meta_info(attrs: &[Option<TokenInfo>]) -> Result<String> {
     Ok(format!("{}\n{}\n\n", kinds, descriptions))
 }
 
+/// Macro that carries the kinds information of the enum, for `ast!` to pass it along with the grammar,
+/// it's `pub(crate)` so `ast!` only works in the crate of the enum
+pub fn generate_ast_callback(token_kind: &str, attrs: &[TokenInfo]) -> Result<String> {
+    let name = crate::ast_macro::callback_name(token_kind);
+
+    let mut kinds = String::new();
+    for attr in attrs {
+        let desc = attr.description.clone().unwrap_or(format!("\"{}\"", attr.variant_name));
+        let tags = [("trivia", attr.trivia), ("error", attr.error)]
+            .into_iter()
+            .filter(|(_, set)| *set)
+            .map(|(tag, _)| format!(", {tag}"))
+            .chain(attr.category.map(|category| format!(", {}", category.name())))
+            .chain(attr.operators.iter().map(|op| format!(", {}", attribute::operator_attribute(op))))
+            .collect::<String>();
+        kinds += &*format!("({}, {}, {desc}{tags}), ", attr.literal, attr.variant_name);
+    }
+
+    Ok(format!(
+        "#[doc(hidden)]\n\
+        #[allow(unused_macros)]\n\
+        macro_rules! {name} {{\n\
+            ($grammar:tt, $($token_kind:tt)*) => {{\n\
+                ::ungrammar_extra_derive::__ast_with_kinds!($grammar, [{kinds}], $($token_kind)*);\n\
+            }};\n\
+        }}\n\
+        #[allow(unused_imports)]\n\
+        pub(crate) use {name};\n"
+    ))
+}
+
+/// `T![...]` macro that maps the literals to their kind (ex: `T![+]`), delimiters go in
+/// char literals (ex: `T!['(']`), `SyntaxKind` is whichever one is in scope where it's called,
+/// it's only there with `#[syntax(token_macro)]` and named `name`
+pub fn generate_token_macro(name: &str, attrs: &[TokenInfo]) -> Result<String> {
+    let mut arms = String::new();
+
+    for attr in attrs {
+        let Some(literal) = attr.literal.strip_prefix('"').and_then(|l| l.strip_suffix('"')) else { continue };
+
+        let pattern = match literal {
+            "(" | ")" | "[" | "]" | "{" | "}" => format!("'{literal}'"),
+            // Literals that aren't tokens, or that would be taken for the macro's own syntax, can't be matched
+            _ if literal.contains(['$', '\\']) || TokenStream::from_str(literal).is_err() => continue,
+            _ => literal.to_owned(),
+        };
+        arms += &*format!("[{pattern}] => {{ SyntaxKind::{} }};\n", attr.variant_name);
+    }
+
+    Ok(format!(
+        "#[allow(unused_macros)]\n\
+        macro_rules! {name} {{\n\
+            {arms}\
+        }}\n\
+        #[allow(unused_imports)]\n\
+        pub(crate) use {name};\n"
+    ))
+}
@@ -4,12 +4,92 @@
 use crate::derive_enum::DeriveEnum;
 
 // External Uses
-//use virtue::prelude::*;
-use eyre::Result;
+use virtue::parse::Generic;
+use virtue::prelude::*;
 
-#[allow(unused)]
 impl DeriveEnum {
-    pub(crate) fn generate_into_impl() -> Result<()> {
-        todo!()
+    /// Generates `From<TokenKind> for SyntaxKind`, and `TryFrom<SyntaxKind> for TokenKind` for the
+    /// way back, which fails on the kinds of variants that hold data since there's no data to give them
+    pub(crate) fn generate_into_impl(
+        &self,
+        generator: &mut Generator,
+        kind_generator: &mut Generator,
+    ) -> Result<()> {
+        let token_kind = generator.target_name().to_string();
+        let (impl_generics, type_generics) = self.split_generics();
+        let token_kind_type = format!("{token_kind}{type_generics}");
+
+        kind_generator
+            .impl_for(format!("From<{token_kind_type}>"))
+            .with_impl_generics(impl_generics)
+            .generate_fn("from")
+            .with_arg("kind", &token_kind_type)
+            .with_return_type("Self")
+            .body(|b| {
+                let mut body = "match kind {".to_string();
+                for variant in &self.variants {
+                    body += &*format!("{token_kind}::{0} {{ .. }} => Self::{0},\n", variant.name);
+                }
+                body += "}";
+
+                b.push_parsed(body)?;
+                Ok(())
+            })?;
+
+        let mut try_from = generator.impl_for("TryFrom<SyntaxKind>");
+        try_from.impl_outer_attr("allow(unreachable_patterns)")?;
+        try_from.impl_type("Error", "SyntaxKind")?;
+        try_from
+            .generate_fn("try_from")
+            .with_arg("kind", "SyntaxKind")
+            .with_return_type("Result<Self, SyntaxKind>")
+            .body(|b| {
+                let mut body = "match kind {".to_string();
+                for variant in self.variants.iter().filter(|v| v.fields.is_none()) {
+                    body += &*format!("SyntaxKind::{0} => Ok(Self::{0}),\n", variant.name);
+                }
+                body += "kind => Err(kind),\n}";
+
+                b.push_parsed(body)?;
+                Ok(())
+            })?;
+
+        Ok(())
+    }
+
+    /// The token kind's generics as they're declared on the impl (ex: `'a`, `T: Clone`) and as
+    /// they're given to the type (ex: `<'a, T>`)
+    fn split_generics(&self) -> (Vec<String>, String) {
+        let Some(generics) = &self.generics else {
+            return (vec![], String::new());
+        };
+        let tokens = |trees: &[TokenTree]| TokenStream::from_iter(trees.iter().cloned()).to_string();
+        let bounded = |name: String, bounds: &[TokenTree]| match bounds.is_empty() {
+            true => name,
+            false => format!("{name}: {}", tokens(bounds)),
+        };
+
+        let mut impl_generics = vec![];
+        let mut names = vec![];
+        for generic in generics.iter() {
+            let (declared, name) = match generic {
+                Generic::Lifetime(lt) => {
+                    let name = format!("'{}", lt.ident);
+                    (bounded(name.clone(), &lt.constraint), name)
+                }
+                Generic::Generic(ty) => {
+                    (bounded(ty.ident.to_string(), &ty.constraints), ty.ident.to_string())
+                }
+                Generic::Const(ct) => (
+                    format!("const {}: {}", ct.ident, tokens(&ct.constraints)),
+                    ct.ident.to_string(),
+                ),
+                _ => unreachable!("virtue only parses lifetimes, types and consts as generics"),
+            };
+            impl_generics.push(declared);
+            names.push(name);
+        }
+
+        (impl_generics, format!("<{}>", names.join(", ")))
     }
 }
@@ -1,4 +1,5 @@
 // Relative Modules
+mod ast_macro;
 mod attribute;
 mod derive_enum;
 mod into_kind_impl;
@@ -24,33 +25,81 @@ pub fn syntax_kind_from_token_kind_derive(
 
 fn syntax_kind_from_token_kind(input: TokenStream) -> Result<TokenStream> {
     let parse = Parse::new(input)?;
+    let generics = match &parse {
+        Parse::Enum { name, generic_constraints: Some(_), .. } => {
+            return Err(Error::custom_at(
+                "SyntaxKind can't be derived for a token kind with a where clause, \
+                bound its generics in place instead (ex: TokenKind<T: Clone>)",
+                name.span(),
+            ));
+        }
+        Parse::Enum { generics, .. } => generics.clone(),
+        _ => None,
+    };
+    let token_macro = match &parse {
+        Parse::Enum { attributes, .. } => attribute::parse_token_macro_attribute(attributes)?,
+        _ => None,
+    };
     let (mut generator, _, body) = parse.into_generator();
+    let (mut kind_generator, _, _) =
+        Parse::new(TokenStream::from_str("enum SyntaxKind {}").unwrap())?.into_generator();
 
-    let derive_enum;
-
-    match body {
-        Body::Enum(body) => {
-            derive_enum = Some(DeriveEnum::new(body.variants).generate_encode_new(&mut generator)?);
-        }
+    let derive_enum = match body {
+        Body::Enum(body) => DeriveEnum::new(body.variants, generics)
+            .generate_encode_new(&mut generator, &mut kind_generator)?,
         Body::Struct(_) => {
-            panic!("Only derive SyntaxKind an enum with Tokens for lexing (TokenKind)")
+            return Err(Error::custom_at(
+                "SyntaxKind can only be derived for an enum of tokens (ex: TokenKind)",
+                generator.target_name().span(),
+            ));
         }
-    }
+    };
 
     // generator.export_to_file("ungrammar-extra-derive", "SyntaxKind");
 
     let mut enum_variants = vec![];
-    for variant in &derive_enum.unwrap().variants {
+    for variant in &derive_enum.variants {
         enum_variants.push(attribute::parse_tokens_info_attribute(
             &variant.attributes,
-            variant.name.to_string(),
+            &variant.name,
         )?);
     }
     
+    let token_kind = generator.target_name().to_string();
+
     let mut output = TokenStream::from_str(
-        &*derive_enum::generate_kinds_meta_info(&enum_variants)?
+        &derive_enum::generate_kinds_meta_info(&enum_variants)?
     ).unwrap();
+    output.extend(TokenStream::from_str(
+        &derive_enum::generate_ast_callback(&token_kind, &enum_variants)?
+    ).unwrap());
+    if let Some(name) = token_macro {
+        output.extend(TokenStream::from_str(
+            &derive_enum::generate_token_macro(&name, &enum_variants)?
+        ).unwrap());
+    }
     output.extend(generator.finish()?);
+    output.extend(kind_generator.finish()?);
 
     Ok(output)
 }
+
+/// Expands a grammar into its AST in place, same as the generator would generate it, the token kind
+/// enum must derive `SyntaxKind` and be given by its path from where the macro is called
+/// ```ignore
+/// mod syntax {
+///     ungrammar_extra_derive::ast!("grammar.ungram", super::TokenKind);
+/// }
+/// ```
+/// The grammar path is relative to the crate's manifest directory, and the enum must be defined
+/// in the same crate, since the macro the derive emits with its kinds is `pub(crate)`
+#[proc_macro]
+pub fn ast(input: TokenStream) -> TokenStream {
+    ast_macro::ast(input).unwrap_or_else(|error| error.into_token_stream())
+}
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn __ast_with_kinds(input: TokenStream) -> TokenStream {
+    ast_macro::ast_with_kinds(input).unwrap_or_else(|error| error.into_token_stream())
+}
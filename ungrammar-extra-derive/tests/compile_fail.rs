@@ -0,0 +1,5 @@
+#[test]
+fn errors_point_at_the_offending_code() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/compile_fail/*.rs");
+}
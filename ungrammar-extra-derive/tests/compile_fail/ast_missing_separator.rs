@@ -0,0 +1,4 @@
+// The arguments the derive's callback macro passes along, with the `,` after the grammar left out
+ungrammar_extra_derive::__ast_with_kinds!("grammar.ungram" [("+", PlusOp, "Plus")], TokenKind);
+
+fn main() {}
@@ -0,0 +1,8 @@
+use ungrammar_extra_derive::SyntaxKind;
+
+#[derive(SyntaxKind)]
+enum TokenKind {
+    Año,
+}
+
+fn main() {}
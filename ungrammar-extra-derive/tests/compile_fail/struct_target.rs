@@ -0,0 +1,8 @@
+use ungrammar_extra_derive::SyntaxKind;
+
+#[derive(SyntaxKind)]
+struct TokenKind {
+    literal: String,
+}
+
+fn main() {}
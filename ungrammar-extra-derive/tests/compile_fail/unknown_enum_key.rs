@@ -0,0 +1,10 @@
+use ungrammar_extra_derive::SyntaxKind;
+
+#[derive(SyntaxKind)]
+#[syntax(token_macros)]
+enum TokenKind {
+    #[syntax(lit="+")]
+    PlusOp,
+}
+
+fn main() {}
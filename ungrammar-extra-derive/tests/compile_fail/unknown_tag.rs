@@ -0,0 +1,9 @@
+use ungrammar_extra_derive::SyntaxKind;
+
+#[derive(SyntaxKind)]
+enum TokenKind {
+    #[syntax(lit="//", comment)]
+    Comment,
+}
+
+fn main() {}
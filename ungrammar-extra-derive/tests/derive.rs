@@ -3,6 +3,7 @@ use ungrammar_extra_derive::SyntaxKind;
 
 
 #[test]
+#[allow(clippy::no_effect)]
 fn derive() {
     #[derive(SyntaxKind)]
     enum TokenKind {
@@ -12,3 +13,87 @@ fn derive() {
 
     TokenKind::StringLit {};
 }
+
+#[test]
+fn token_kind_converts_to_and_from_syntax_kind() {
+    #[derive(Debug, PartialEq, SyntaxKind)]
+    enum TokenKind {
+        #[syntax(lit="+")]
+        PlusOp,
+
+        #[syntax(lit="int")]
+        IntLit(i64),
+    }
+
+    assert_eq!(SyntaxKind::from(TokenKind::PlusOp), SyntaxKind::PlusOp);
+    assert_eq!(SyntaxKind::from(TokenKind::IntLit(1)), SyntaxKind::IntLit);
+
+    let kind: SyntaxKind = TokenKind::IntLit(2).into();
+    assert_eq!(kind, SyntaxKind::IntLit);
+
+    // The value of the integer is not part of the kind, so there's no token to go back to
+    assert_eq!(TokenKind::try_from(SyntaxKind::PlusOp), Ok(TokenKind::PlusOp));
+    assert_eq!(TokenKind::try_from(SyntaxKind::IntLit), Err(SyntaxKind::IntLit));
+}
+
+#[test]
+fn token_macro_maps_literals_to_kinds() {
+    #[derive(SyntaxKind)]
+    #[syntax(token_macro)]
+    enum TokenKind {
+        #[syntax(lit="+")]
+        PlusOp,
+
+        #[syntax(lit="->")]
+        Arrow,
+
+        #[syntax(lit="(")]
+        LParen,
+
+        #[syntax(lit="int")]
+        IntLit,
+
+        #[syntax(lit="fn")]
+        FnKw,
+    }
+
+    assert_eq!(T![+], SyntaxKind::PlusOp);
+    assert_eq!(T![->], SyntaxKind::Arrow);
+    assert_eq!(T!['('], SyntaxKind::LParen);
+    assert_eq!(T![int], SyntaxKind::IntLit);
+    assert_eq!(T![fn], SyntaxKind::FnKw);
+}
+
+#[test]
+fn token_macro_can_be_named() {
+    // The user's own `T!`, which the derive leaves alone
+    macro_rules! T {
+        [+] => { "plus" };
+    }
+
+    #[derive(SyntaxKind)]
+    #[syntax(token_macro = "Tok")]
+    enum TokenKind {
+        #[syntax(lit="+")]
+        PlusOp,
+    }
+
+    assert_eq!(Tok![+], SyntaxKind::PlusOp);
+    assert_eq!(T![+], "plus");
+}
+
+#[test]
+fn token_kind_with_a_lifetime_converts_to_syntax_kind() {
+    #[derive(Debug, PartialEq, SyntaxKind)]
+    enum TokenKind<'a> {
+        #[syntax(lit="+")]
+        PlusOp,
+
+        #[syntax(lit="ident")]
+        Ident(&'a str),
+    }
+
+    assert_eq!(SyntaxKind::from(TokenKind::Ident("x")), SyntaxKind::Ident);
+    assert_eq!(TokenKind::try_from(SyntaxKind::PlusOp), Ok(TokenKind::PlusOp));
+    assert_eq!(TokenKind::try_from(SyntaxKind::Ident), Err(SyntaxKind::Ident));
+}
@@ -5,6 +5,7 @@ use ungrammar_extra_derive::SyntaxKind;
 
 
 #[test]
+#[allow(clippy::no_effect)]
 fn logos_derived() {
     #[derive(SyntaxKind, Logos)]
     enum TokenKind {
@@ -1,15 +1,115 @@
 // Standard USes
 use std::collections::HashMap;
 
+// Relative Modules
+#[cfg(feature = "parser")]
+pub mod parser;
+
 // Local Uses
 
 // External Uses
+pub use text_size::{TextRange, TextSize};
 
 
 pub struct SyntaxKindMeta {
     pub name: String,
     pub literal: String,
-    pub description: String
+    pub description: String,
+    /// Kept in the tree even though the grammar doesn't mention it (ex: whitespace, comments)
+    pub trivia: bool,
+    /// Given to the tokens the lexer couldn't make sense of
+    pub error: bool,
+    pub category: Option<TokenCategory>,
+    /// Operators the kind is, a kind can be several (ex: prefix and infix `-`)
+    pub operators: Vec<Operator>,
+}
+
+/// What kind of token a kind is, for the tools that treat them differently (ex: highlighters)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenCategory {
+    Keyword,
+    Punct,
+    Literal,
+    Ident,
+}
+
+impl TokenCategory {
+    pub const ALL: [Self; 4] = [Self::Keyword, Self::Punct, Self::Literal, Self::Ident];
+
+    /// Name of the category, as written in `#[syntax(category = "...")]`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Keyword => "keyword",
+            Self::Punct => "punct",
+            Self::Literal => "literal",
+            Self::Ident => "ident",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> { Self::ALL.into_iter().find(|c| c.name() == name) }
+}
+
+/// Precedence of an operator kind, marked with `#[syntax(infix(prec = 10, assoc = "left"))]`,
+/// `#[syntax(prefix(prec = 20))]` or `#[syntax(postfix(prec = 30))]`, the higher binds tighter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Operator {
+    pub fixity: Fixity,
+    prec: u8,
+}
+
+impl Operator {
+    /// Panics when `prec` is `u8::MAX`, which leaves no room for the binding powers,
+    /// in a `const` (ex: the table of [`KindsMetaInfo::operators`]) that's a compile error
+    pub const fn new(fixity: Fixity, prec: u8) -> Self {
+        assert!(prec < u8::MAX, "Precedence must be below u8::MAX");
+        Self { fixity, prec }
+    }
+
+    pub fn prec(self) -> u8 { self.prec }
+
+    /// Left and right binding powers for a Pratt parser, there's none on the side without an operand,
+    /// and the side an infix operator associates to gets the lower one
+    pub fn binding_power(self) -> (Option<u8>, Option<u8>) {
+        match self.fixity {
+            Fixity::Infix(Assoc::Left) => (Some(self.prec), Some(self.prec + 1)),
+            Fixity::Infix(Assoc::Right) => (Some(self.prec + 1), Some(self.prec)),
+            Fixity::Prefix => (None, Some(self.prec)),
+            Fixity::Postfix => (Some(self.prec), None),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fixity {
+    Infix(Assoc),
+    Prefix,
+    Postfix,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Assoc {
+    Left,
+    Right,
+}
+
+impl Assoc {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "left" => Some(Self::Left),
+            "right" => Some(Self::Right),
+            _ => None,
+        }
+    }
+}
+
+/// What the parser runtime needs to know about a kind of the tree, the generated `kind::SyntaxKind`
+/// implements it from the kinds' meta information
+pub trait TreeKind: Copy {
+    /// Tokens the grammar doesn't mention, they can be anywhere in the tree (ex: whitespace, comments)
+    fn is_trivia(self) -> bool;
+
+    /// Name of the kind in messages (ex: the parse errors), the description of a token kind
+    fn description(self) -> &'static str;
 }
 
 pub trait KindsMetaInfo {
@@ -18,6 +118,28 @@ pub trait KindsMetaInfo {
     fn kinds() -> &'static HashMap<&'static str, &'static str>;
     fn descriptions() -> &'static HashMap<&'static str, &'static str>;
 
+    /// Literals of the trivia kinds, marked with `#[syntax(trivia)]`
+    fn trivia() -> &'static [&'static str] { &[] }
+    /// Literals of the error kinds, marked with `#[syntax(error)]`
+    fn errors() -> &'static [&'static str] { &[] }
+
+    /// Category of each literal that has one, marked with `#[syntax(category = "...")]`
+    fn categories() -> &'static [(&'static str, TokenCategory)] { &[] }
+
+    /// Operators of each literal that is one, a literal can be in it more than once
+    fn operators() -> &'static [(&'static str, Operator)] { &[] }
+
+    fn is_trivia(literal: &str) -> bool { Self::trivia().contains(&literal) }
+    fn is_error(literal: &str) -> bool { Self::errors().contains(&literal) }
+
+    fn category(literal: &str) -> Option<TokenCategory> {
+        Self::categories().iter().find(|(lit, _)| *lit == literal).map(|(_, category)| *category)
+    }
+
+    fn operators_of(literal: &str) -> Vec<Operator> {
+        Self::operators().iter().filter(|(lit, _)| *lit == literal).map(|(_, operator)| *operator).collect()
+    }
+
     /*
     fn kind(name: &str) -> Option<&str> {
         Self::kinds().iter().filter(|k| **k == name).next().copied()
@@ -0,0 +1,23 @@
+// Standard Uses
+
+// Crate Uses
+use crate::parser::ParseError;
+
+// External Uses
+
+
+/// What the parser did, in order, the tree is only built from them once parsing is done so
+/// nodes can be started after their first children (see [`super::CompletedMarker::precede`])
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<K> {
+    /// Starts a node, `forward_parent` is the distance to the event of a node that
+    /// was started later but wraps this one
+    Start { kind: K, forward_parent: Option<usize> },
+    Finish,
+    /// Adds the next token to the tree as the given kind
+    Token { kind: K },
+    Error(ParseError<K>),
+    /// A node that is not completed yet or was abandoned, or the start of a node that
+    /// was already started along with the node it precedes
+    Tombstone,
+}
@@ -0,0 +1,71 @@
+// Standard Uses
+
+// Crate Uses
+use crate::TreeKind;
+use crate::parser::{event::Event, Parser};
+
+// External Uses
+use cstree::Syntax;
+
+
+/// A node that was started, it has to be either completed or abandoned
+#[must_use = "a marker has to be either completed or abandoned"]
+pub struct Marker {
+    position: usize,
+    done: bool,
+}
+
+impl Marker {
+    pub(crate) fn new(position: usize) -> Self { Self { position, done: false } }
+
+    /// Finishes the node, with everything parsed since it started as its children
+    pub fn complete<K: Syntax + TreeKind>(mut self, parser: &mut Parser<K>, kind: K) -> CompletedMarker<K> {
+        self.done = true;
+
+        parser.events[self.position] = Event::Start { kind, forward_parent: None };
+        parser.events.push(Event::Finish);
+
+        CompletedMarker { position: self.position, kind }
+    }
+
+    /// Drops the node, whatever was parsed since it started goes to the parent node
+    pub fn abandon<K: Syntax + TreeKind>(mut self, parser: &mut Parser<K>) {
+        self.done = true;
+
+        // Nothing was parsed after it, so it can go away, otherwise it stays a tombstone
+        if self.position == parser.events.len() - 1 {
+            parser.events.pop();
+        }
+    }
+}
+
+impl Drop for Marker {
+    fn drop(&mut self) {
+        if !self.done && !std::thread::panicking() {
+            panic!("a marker has to be either completed or abandoned");
+        }
+    }
+}
+
+/// A node that was completed, which can still be wrapped by a new one
+#[derive(Debug, Clone, Copy)]
+pub struct CompletedMarker<K> {
+    position: usize,
+    kind: K,
+}
+
+impl<K: Syntax + TreeKind> CompletedMarker<K> {
+    pub fn kind(&self) -> K { self.kind }
+
+    /// Starts a node that wraps this one, (ex: the binary expression `1 + 2` after parsing `1`)
+    pub fn precede(self, parser: &mut Parser<K>) -> Marker {
+        let marker = parser.start();
+
+        match &mut parser.events[self.position] {
+            Event::Start { forward_parent, .. } => *forward_parent = Some(marker.position - self.position),
+            _ => unreachable!("completed markers always point at their start event"),
+        }
+
+        marker
+    }
+}
@@ -0,0 +1,127 @@
+// Relative Modules
+pub mod event;
+pub mod marker;
+pub mod sink;
+
+// Standard Uses
+use std::fmt;
+
+// Crate Uses
+use crate::TreeKind;
+use crate::parser::{event::Event, marker::Marker};
+pub use crate::parser::marker::CompletedMarker;
+
+// External Uses
+use cstree::{syntax::ResolvedNode, Syntax};
+
+
+/// Parser over a slice of lexed tokens, in the style of rust-analyzer's, it only records
+/// events which are turned into a tree once it's done (see [`sink::build_tree`]), trivia
+/// is never seen by the parser, the sink puts it back in the tree. The kind has to implement
+/// [`TreeKind`] along with cstree's `Syntax`, since its trivia kinds are what's skipped
+pub struct Parser<'t, K> {
+    tokens: &'t [(K, &'t str)],
+    /// Indices in `tokens` of the tokens that aren't trivia
+    significant: Vec<usize>,
+    position: usize,
+    pub(crate) events: Vec<Event<K>>,
+}
+
+impl<'t, K: Syntax + TreeKind> Parser<'t, K> {
+    pub fn new(tokens: &'t [(K, &'t str)]) -> Self {
+        let significant = (0..tokens.len()).filter(|i| !tokens[*i].0.is_trivia()).collect();
+        Self { tokens, significant, position: 0, events: vec![] }
+    }
+
+    /// Kind of the token `n` tokens ahead, trivia aside, `None` past the end
+    pub fn nth(&self, n: usize) -> Option<K> {
+        self.significant.get(self.position + n).map(|index| self.tokens[*index].0)
+    }
+
+    pub fn current(&self) -> Option<K> { self.nth(0) }
+
+    pub fn at(&self, kind: K) -> bool { self.current() == Some(kind) }
+
+    pub fn at_any(&self, kinds: &[K]) -> bool { self.current().is_some_and(|kind| kinds.contains(&kind)) }
+
+    pub fn at_end(&self) -> bool { self.current().is_none() }
+
+    /// Starts a node, its kind is only given once it's completed
+    pub fn start(&mut self) -> Marker {
+        // Stays a tombstone until the marker is completed
+        self.events.push(Event::Tombstone);
+        Marker::new(self.events.len() - 1)
+    }
+
+    /// Adds the current token to the tree, does nothing at the end
+    pub fn bump(&mut self) {
+        if let Some(kind) = self.current() { self.bump_as(kind) }
+    }
+
+    /// Adds the current token to the tree as another kind, (ex: a contextual keyword lexed as an identifier)
+    pub fn bump_as(&mut self, kind: K) {
+        if self.at_end() { return }
+
+        self.events.push(Event::Token { kind });
+        self.position += 1;
+    }
+
+    /// Bumps the token if it's of the kind
+    pub fn eat(&mut self, kind: K) -> bool {
+        if !self.at(kind) { return false }
+
+        self.bump();
+        true
+    }
+
+    /// Bumps the token if it's of the kind, otherwise reports it as missing
+    pub fn expect(&mut self, kind: K) -> bool {
+        if self.eat(kind) { return true }
+
+        self.error(&[kind]);
+        false
+    }
+
+    /// Reports that one of the kinds was expected at the current token
+    pub fn error(&mut self, expected: &[K]) {
+        let position = self.significant.get(self.position).copied().unwrap_or(self.tokens.len());
+        let error = ParseError { position, expected: expected.to_vec(), found: self.current() };
+        self.events.push(Event::Error(error));
+    }
+
+    /// Events recorded so far, for when the tree is built with something else than [`sink::build_tree`]
+    pub fn events(&self) -> &[Event<K>] { &self.events }
+
+    /// Builds the tree, every token has to be bumped by then, and inside a single root node
+    pub fn finish(self) -> Parse<K> { sink::build_tree(self.tokens, self.events) }
+}
+
+/// A tree along with the errors found while parsing it
+#[derive(Debug)]
+pub struct Parse<K: Syntax> {
+    pub root: ResolvedNode<K>,
+    pub errors: Vec<ParseError<K>>,
+}
+
+/// A token that was expected but not found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError<K> {
+    /// Index of the token in the input, which is the input length when it ended early
+    pub position: usize,
+    pub expected: Vec<K>,
+    pub found: Option<K>,
+}
+
+impl<K: Syntax + TreeKind> fmt::Display for ParseError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let expected: Vec<_> = self.expected.iter().map(|kind| kind.description()).collect();
+        match (expected.as_slice(), self.found) {
+            ([], Some(found)) => write!(f, "Unexpected {}", found.description()),
+            ([], None) => write!(f, "Unexpected end of input"),
+            (expected, Some(found)) => write!(f, "Expected {}, found {}", expected.join(" or "), found.description()),
+            (expected, None) => write!(f, "Expected {}, found the end of input", expected.join(" or ")),
+        }
+    }
+}
+
+impl<K: Syntax + TreeKind> std::error::Error for ParseError<K> {}
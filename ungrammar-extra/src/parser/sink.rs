@@ -0,0 +1,71 @@
+// Standard Uses
+use std::{iter::Peekable, slice::Iter};
+
+// Crate Uses
+use crate::TreeKind;
+use crate::parser::{event::Event, Parse};
+
+// External Uses
+use cstree::{build::GreenNodeBuilder, syntax::SyntaxNode, Syntax};
+
+
+/// Builds the tree out of the parser events, the text of every token comes from `tokens`,
+/// trivia goes right where it is in between them, but always inside the root node
+pub fn build_tree<K: Syntax + TreeKind>(tokens: &[(K, &str)], mut events: Vec<Event<K>>) -> Parse<K> {
+    let mut builder = GreenNodeBuilder::new();
+    let mut tokens = tokens.iter().peekable();
+    let mut errors = vec![];
+    let mut depth = 0;
+
+    for index in 0..events.len() {
+        match std::mem::replace(&mut events[index], Event::Tombstone) {
+            Event::Start { kind, forward_parent } => {
+                // Nodes that precede this one were started later, but wrap it, so they go first
+                let mut kinds = vec![kind];
+                let (mut index, mut forward_parent) = (index, forward_parent);
+
+                while let Some(distance) = forward_parent {
+                    index += distance;
+                    forward_parent = match std::mem::replace(&mut events[index], Event::Tombstone) {
+                        Event::Start { kind, forward_parent } => {
+                            kinds.push(kind);
+                            forward_parent
+                        },
+                        _ => unreachable!("forward parents always point at start events"),
+                    };
+                }
+
+                // Trivia before the root node has nowhere else to go than inside it
+                if depth > 0 { push_trivia(&mut builder, &mut tokens) }
+                for kind in kinds.into_iter().rev() {
+                    builder.start_node(kind);
+                    depth += 1;
+                }
+            },
+            Event::Finish => {
+                depth -= 1;
+                if depth == 0 { push_trivia(&mut builder, &mut tokens) }
+                builder.finish_node();
+            },
+            Event::Token { kind } => {
+                push_trivia(&mut builder, &mut tokens);
+                let (_, text) = tokens.next().expect("the parser only bumps the tokens it was given");
+                builder.token(kind, text);
+            },
+            Event::Error(error) => errors.push(error),
+            Event::Tombstone => {},
+        }
+    }
+
+    let (green, cache) = builder.finish();
+    let interner = cache.and_then(|cache| cache.into_interner()).expect("the builder owns its interner");
+
+    Parse { root: SyntaxNode::new_root_with_resolver(green, interner), errors }
+}
+
+/// Adds the trivia tokens that come next
+fn push_trivia<K: Syntax + TreeKind>(builder: &mut GreenNodeBuilder<K>, tokens: &mut Peekable<Iter<(K, &str)>>) {
+    while let Some((kind, text)) = tokens.next_if(|(kind, _)| kind.is_trivia()) {
+        builder.token(*kind, text);
+    }
+}
@@ -1,8 +1,11 @@
+// The tests spell out what the derive generates, (ex: `&*` on the lazy maps)
+#![allow(dead_code, clippy::explicit_auto_deref)]
+
 // Standard Uses
 use std::collections::HashMap;
 
 // External Uses
-use ungrammar_extra::KindsMetaInfo;
+use ungrammar_extra::{Assoc, Fixity, KindsMetaInfo, Operator};
 
 
 #[test]
@@ -45,3 +48,44 @@ fn literal_of_kind() {
     pub enum TokenKind {}
 
 }
+
+#[test]
+fn trivia_of_kind() {
+    pub enum TokenKind {}
+
+    static TOKENKIND_KINDS: once_cell::sync::Lazy<HashMap<&'static str, &'static str>> = once_cell::sync::Lazy::new(||
+        HashMap::from([
+            ("foo", "Foo"),
+            ("whitespace", "Whitespace"),
+        ])
+    );
+
+    impl KindsMetaInfo for TokenKind {
+        fn literals() -> &'static [&'static str] {&[
+            "foo", "whitespace"
+        ]}
+
+        fn kinds() -> &'static HashMap<&'static str, &'static str> { &TOKENKIND_KINDS }
+
+        fn descriptions() -> &'static HashMap<&'static str, &'static str> { &TOKENKIND_KINDS }
+
+        fn trivia() -> &'static [&'static str] { &["whitespace"] }
+    }
+
+    assert!(TokenKind::is_trivia("whitespace"));
+    assert!(!TokenKind::is_trivia("foo"));
+    assert!(!TokenKind::is_error("whitespace"));
+}
+
+#[test]
+fn operator_binding_powers() {
+    assert_eq!(Operator::new(Fixity::Infix(Assoc::Left), 10).binding_power(), (Some(10), Some(11)));
+    assert_eq!(Operator::new(Fixity::Infix(Assoc::Right), 254).binding_power(), (Some(255), Some(254)));
+    assert_eq!(Operator::new(Fixity::Prefix, 30).binding_power(), (None, Some(30)));
+}
+
+#[test]
+#[should_panic(expected = "Precedence must be below u8::MAX")]
+fn operator_precedence_leaves_room_for_associativity() {
+    Operator::new(Fixity::Infix(Assoc::Left), u8::MAX);
+}
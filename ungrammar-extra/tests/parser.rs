@@ -0,0 +1,125 @@
+// External Uses
+use cstree::{syntax::SyntaxNode, util::NodeOrToken};
+use ungrammar_extra::{parser::{CompletedMarker, Parser}, TreeKind};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, cstree::Syntax)]
+#[repr(u32)]
+enum SyntaxKind {
+    IntLit,
+    PlusOp,
+    StarOp,
+    Whitespace,
+    Literal,
+    BinExpr,
+    Root,
+}
+
+impl TreeKind for SyntaxKind {
+    fn is_trivia(self) -> bool { self == Self::Whitespace }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::IntLit => "Integer",
+            Self::PlusOp => "Plus",
+            Self::StarOp => "Star",
+            Self::Whitespace => "Whitespace",
+            Self::Literal => "Literal",
+            Self::BinExpr => "BinExpr",
+            Self::Root => "Root",
+        }
+    }
+}
+
+/// Binary expressions by binding power, `precede` wraps the left hand side once the operator is seen
+fn expr(p: &mut Parser<SyntaxKind>, min_power: u8) -> Option<CompletedMarker<SyntaxKind>> {
+    let literal = p.start();
+    if !p.expect(SyntaxKind::IntLit) {
+        literal.abandon(p);
+        return None;
+    }
+    let mut lhs = literal.complete(p, SyntaxKind::Literal);
+
+    loop {
+        let power = match p.current() {
+            Some(SyntaxKind::PlusOp) => 1,
+            Some(SyntaxKind::StarOp) => 2,
+            _ => break,
+        };
+        if power < min_power { break }
+
+        let bin_expr = lhs.precede(p);
+        p.bump();
+        expr(p, power + 1);
+        lhs = bin_expr.complete(p, SyntaxKind::BinExpr);
+    }
+
+    Some(lhs)
+}
+
+fn parse(tokens: &[(SyntaxKind, &str)]) -> ungrammar_extra::parser::Parse<SyntaxKind> {
+    let mut p = Parser::new(tokens);
+    let root = p.start();
+    expr(&mut p, 0);
+    root.complete(&mut p, SyntaxKind::Root);
+
+    p.finish()
+}
+
+fn shape(node: &SyntaxNode<SyntaxKind>) -> String {
+    let children: Vec<_> = node.children_with_tokens()
+        .map(|child| match child {
+            NodeOrToken::Node(node) => shape(node),
+            NodeOrToken::Token(token) => format!("{:?}", token.kind()),
+        })
+        .collect();
+
+    format!("{:?}({})", node.kind(), children.join(" "))
+}
+
+
+#[test]
+fn precede_wraps_completed_nodes() {
+    use SyntaxKind::*;
+
+    let parse = parse(&[(IntLit, "1"), (PlusOp, "+"), (IntLit, "2"), (StarOp, "*"), (IntLit, "3")]);
+
+    assert!(parse.errors.is_empty(), "{:?}", parse.errors);
+    assert_eq!(
+        shape(parse.root.syntax()),
+        "Root(BinExpr(Literal(IntLit) PlusOp BinExpr(Literal(IntLit) StarOp Literal(IntLit))))"
+    );
+}
+
+#[test]
+fn errors_are_described_with_the_kind_descriptions() {
+    use SyntaxKind::*;
+
+    let parse = parse(&[(IntLit, "1"), (PlusOp, "+"), (StarOp, "*")]);
+
+    let errors: Vec<_> = parse.errors.iter().map(|e| (e.position, e.to_string())).collect();
+    assert_eq!(errors, [
+        (2, "Expected Integer, found Star".to_owned()),
+        (3, "Expected Integer, found the end of input".to_owned()),
+    ]);
+}
+
+#[test]
+fn trivia_is_skipped_and_kept_in_the_tree() {
+    use SyntaxKind::*;
+
+    let tree = parse(&[
+        (Whitespace, " "), (IntLit, "1"), (Whitespace, " "), (PlusOp, "+"), (Whitespace, " "), (IntLit, "2"), (Whitespace, "\n")
+    ]);
+
+    assert!(tree.errors.is_empty(), "{:?}", tree.errors);
+    assert_eq!(
+        shape(tree.root.syntax()),
+        "Root(Whitespace BinExpr(Literal(IntLit) Whitespace PlusOp Whitespace Literal(IntLit)) Whitespace)"
+    );
+    assert_eq!(tree.root.to_string(), " 1 + 2\n");
+
+    // Error positions are still indices in the whole input
+    let errors = parse(&[(IntLit, "1"), (Whitespace, " "), (PlusOp, "+"), (Whitespace, " "), (StarOp, "*")]).errors;
+    assert_eq!(errors[0].position, 4);
+}
@@ -0,0 +1,37 @@
+// Standard Uses
+use std::path::{Path, PathBuf};
+
+// Crate Uses
+#[path = "src/generator/token_kind.rs"]
+#[allow(dead_code)]
+mod token_kind;
+
+// External Uses
+use ungrammar_gen::generator::{GeneratorOptions, HandwrittenNode, KindsMeta};
+
+
+
+/// Generates the calculator parser and an AST with a hand-written node, so the tests can compile them
+fn main() {
+	let grammar_path = Path::new("_data_/calculator.ungram");
+	let out_dir = PathBuf::from(std::env::var_os("OUT_DIR").unwrap());
+
+	println!("cargo:rerun-if-changed={}", grammar_path.display());
+	println!("cargo:rerun-if-changed=src/generator/token_kind.rs");
+
+	ungrammar_gen::generator_new::from_path::<token_kind::SyntaxKind>(grammar_path, &out_dir).unwrap();
+
+	// The parser has inner attributes, so it can't be `include!`d but it can be a module by its path
+	let parser_mod = format!("#[path = {:?}]\npub mod parser;\n", out_dir.join("parser.rs"));
+	std::fs::write(out_dir.join("parser_mod.rs"), parser_mod).unwrap();
+
+	// The group is left to be written by hand, the tests write it and compile the rest around it
+	let options = GeneratorOptions {
+		handwritten: vec![HandwrittenNode { node: "Group".into(), replacement: None }],
+		..Default::default()
+	};
+	ungrammar_gen::build::generate_into_with_meta(
+		&out_dir, "_data_/handwritten.ungram",
+		&KindsMeta::from_info::<token_kind::SyntaxKind>(), "crate::generator::TokenKind", &options
+	).unwrap();
+}
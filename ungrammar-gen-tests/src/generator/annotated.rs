@@ -0,0 +1,36 @@
+// External Uses
+use ungrammar_extra_derive::SyntaxKind;
+
+
+/// The shared token kinds with the annotations the derive supports, the plain ones are left as is
+#[derive(SyntaxKind)]
+pub enum TokenKind {
+	#[syntax(lit="string", desc="String", category="literal")]
+	StringLit,
+
+	#[syntax(lit="+", desc="Plus", category="punct", infix(prec = 10))]
+	PlusOp,
+
+	#[syntax(lit="-", desc="Minus", category="punct", infix(prec = 10, assoc = "left"), prefix(prec = 30))]
+	MinusOp,
+
+	#[syntax(lit="int", desc="Integer", category="literal")]
+	IntLit,
+
+	#[syntax(lit="float", desc="Float", category="literal")]
+	FloatLit,
+
+	#[syntax(lit="whitespace", trivia)]
+	Whitespace,
+
+	#[syntax(lit="comment", desc="Comment", trivia)]
+	Comment,
+
+	#[syntax(lit="error", error)]
+	Error,
+}
+
+/// The calculator grammar over the annotated kinds, for the tests that only read the generated code
+pub mod calculator {
+	ungrammar_extra_derive::ast!("_data_/calculator.ungram", super::TokenKind);
+}
@@ -0,0 +1,30 @@
+// External Uses
+use cstree::build::GreenNodeBuilder;
+
+
+
+mod calculator {
+	ungrammar_extra_derive::ast!("_data_/calculator.ungram", super::super::TokenKind);
+}
+
+#[test]
+fn ast_macro_expands_the_grammar_in_place() {
+	use calculator::{ast::*, kind::SyntaxKind, token::*, AstElement, AstToken, SyntaxNode};
+
+	let mut builder = GreenNodeBuilder::<SyntaxKind>::new();
+	builder.start_node(SyntaxKind::Expr);
+	builder.token(SyntaxKind::IntLit, "1");
+	builder.token(SyntaxKind::PlusOp, "+");
+	builder.token(SyntaxKind::FloatLit, "2.0");
+	builder.finish_node();
+	let (green, _) = builder.finish();
+
+	let expr = Expr::cast(SyntaxNode::new_root(green).into()).unwrap();
+
+	assert!(matches!(expr.lhs(), Some(Literal::IntLit(_))));
+	assert!(matches!(expr.op(), Some(InfixOperator::PlusOp(PlusOp { .. }))));
+	assert!(matches!(expr.rhs(), Some(Literal::FloatLit(_))));
+
+	// The tree was built without a resolver, so there's no text to give
+	assert!(matches!(expr.op(), Some(InfixOperator::PlusOp(op)) if op.text().is_none()));
+}
@@ -0,0 +1,22 @@
+// External Uses
+use ungrammar_gen::build;
+
+
+
+#[test]
+fn build_generates_an_includable_file_once() {
+	let temp_dir = super::TempDir::new("build");
+	let out_dir = temp_dir.path();
+
+	let path = build::generate_into::<super::SyntaxKind, super::TokenKind>(out_dir, "_data_/calculator.ungram").unwrap();
+	assert_eq!(path, out_dir.join("calculator.rs"));
+
+	let text = std::fs::read_to_string(&path).unwrap();
+	assert!(!text.contains("#!["), "Inner attributes can't be included\n{text}");
+	assert!(!text.contains("pub mod ast;"), "Modules must be inlined\n{text}");
+	assert!(text.contains("pub mod ast {"), "{text}");
+
+	let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+	build::generate_into::<super::SyntaxKind, super::TokenKind>(out_dir, "_data_/calculator.ungram").unwrap();
+	assert_eq!(std::fs::metadata(&path).unwrap().modified().unwrap(), modified, "Unchanged file was rewritten");
+}
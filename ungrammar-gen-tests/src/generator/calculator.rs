@@ -20,5 +20,5 @@ fn parse_calculator_ungrammar_and_generate_code() {
 		std::fs::create_dir_all(&output_path).unwrap();
 	}
 
-	generator::from_path::<super::SyntaxKind, super::TokenKind>(&grammar_path, &output_path).unwrap();
+	generator::from_path::<super::SyntaxKind, super::TokenKind>(grammar_path, &output_path).unwrap();
 }
@@ -0,0 +1,22 @@
+// External Uses
+use ungrammar_extra::{KindsMetaInfo, TokenCategory};
+
+
+
+#[test]
+fn kinds_meta_info_knows_the_categories() {
+	assert_eq!(super::annotated::SyntaxKind::category("+"), Some(TokenCategory::Punct));
+	assert_eq!(super::annotated::SyntaxKind::category("int"), Some(TokenCategory::Literal));
+	assert_eq!(super::annotated::SyntaxKind::category("whitespace"), None);
+}
+
+#[test]
+fn syntax_kind_tells_the_categories_apart() {
+	use super::annotated::calculator::kind::SyntaxKind;
+
+	assert!(SyntaxKind::PlusOp.is_punct());
+	assert!(SyntaxKind::IntLit.is_literal());
+	assert!(!SyntaxKind::IntLit.is_punct());
+	assert!(!SyntaxKind::Whitespace.is_keyword());
+	assert!(!SyntaxKind::Expr.is_literal());
+}
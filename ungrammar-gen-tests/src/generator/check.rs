@@ -0,0 +1,28 @@
+// Standard Uses
+use std::path::Path;
+
+// External Uses
+use ungrammar_gen::generator::{self, GenError};
+
+
+
+#[test]
+fn check_fails_when_generated_code_is_out_of_date() {
+	let grammar_path = Path::new("_data_/calculator.ungram");
+	// Kept out of the generated crate, since other tests clean it up while running
+	let output_dir = super::TempDir::new("check");
+	let output_path = output_dir.path();
+
+	let check = || generator::check::<super::SyntaxKind, super::TokenKind>(grammar_path, output_path);
+
+	let Err(GenError::Stale(missing)) = check() else { panic!("Expected every file to be missing") };
+	assert!(missing.contains(&output_path.join("ast.rs")), "{missing:?}");
+
+	generator::from_path::<super::SyntaxKind, super::TokenKind>(grammar_path, output_path).unwrap();
+	check().unwrap();
+
+	std::fs::write(output_path.join("token.rs"), "// edited by hand\n").unwrap();
+	let result = check();
+	let Err(GenError::Stale(stale)) = &result else { panic!("Expected stale files, got {result:?}") };
+	assert_eq!(stale, &[output_path.join("token.rs")]);
+}
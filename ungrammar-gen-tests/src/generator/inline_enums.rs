@@ -0,0 +1,33 @@
+mod inline {
+	ungrammar_extra_derive::ast!("_data_/inline.ungram", super::super::TokenKind);
+}
+
+
+#[test]
+fn inline_alternations_become_enums() {
+	use inline::{ast::*, make, printer::ToSource, AstToken};
+
+	let expr = make::bin_expr(
+		Literal::IntLit(make::int_lit("1")),
+		BinExprOp::MinusOp(make::minus_op("-")),
+		Literal::IntLit(make::int_lit("2")),
+	).unwrap();
+
+	let Some(BinExprOp::MinusOp(op)) = expr.op() else { panic!("expected a minus, got {:?}", expr.op()) };
+	assert_eq!(op.text(), Some("-"));
+	assert_eq!(expr.to_source(), "1 - 2");
+}
+
+#[test]
+fn repeated_inline_alternations_become_many_fields() {
+	use inline::{ast::*, make, printer::ToSource};
+
+	let list = make::list([
+		ListItems::Literal(Literal::IntLit(make::int_lit("1"))),
+		ListItems::Neg(make::neg(make::minus_op("-"), Literal::FloatLit(make::float_lit("2.5"))).unwrap()),
+	]).unwrap();
+
+	let items: Vec<_> = list.items().collect();
+	assert!(matches!(items.as_slice(), [ListItems::Literal(_), ListItems::Neg(_)]), "{items:?}");
+	assert_eq!(list.to_source(), "1 - 2.5");
+}
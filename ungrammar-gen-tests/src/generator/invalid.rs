@@ -0,0 +1,55 @@
+// Standard Uses
+use std::path::Path;
+
+// External Uses
+use ungrammar_gen::generator::{self, DiagnosticKind, GenError, Position};
+
+
+
+#[test]
+fn reports_every_lowering_problem() {
+	let grammar_path = Path::new("_data_/invalid.ungram");
+	let output_dir = super::TempDir::new("invalid");
+	let output_path = output_dir.path();
+
+	let result = generator::from_path::<super::SyntaxKind, super::TokenKind>(grammar_path, output_path);
+
+	let Err(GenError::Lowering(diagnostics)) = result else {
+		panic!("Expected lowering diagnostics, got {result:?}")
+	};
+
+	assert_eq!(diagnostics.len(), 7, "{diagnostics:#?}");
+
+	assert_eq!(diagnostics[0].node, "Expr");
+	assert!(matches!(diagnostics[0].kind, DiagnosticKind::UnlabeledAlternation));
+
+	assert_eq!(diagnostics[1].node, "Literal");
+	let DiagnosticKind::UnknownToken { literal, position, suggestions } = &diagnostics[1].kind else {
+		panic!("Expected an unknown token, got {:?}", diagnostics[1].kind)
+	};
+	assert_eq!(literal, "flaot");
+	assert_eq!(*position, Some(Position { line: 5, column: 19 }));
+	assert_eq!(suggestions, &["float"]);
+
+	assert_eq!(diagnostics[2].node, "Signs");
+	assert!(matches!(diagnostics[2].kind, DiagnosticKind::UnsupportedRepetition));
+
+	// A field after a repetition of its type would only ever find the repeated elements
+	assert_eq!(diagnostics[3].node, "Tail");
+	assert!(matches!(&diagnostics[3].kind, DiagnosticKind::DuplicateType { ty } if ty == "Literal"));
+
+	assert_eq!(diagnostics[4].node, "Minuses");
+	assert!(matches!(&diagnostics[4].kind, DiagnosticKind::DuplicateType { ty } if ty == "MinusOp"));
+
+	// The first '%' is in an alternation that isn't lowered, so the one reported is the second
+	assert_eq!(diagnostics[5].node, "Rem");
+	assert!(matches!(diagnostics[5].kind, DiagnosticKind::UnlabeledAlternation));
+	assert_eq!(diagnostics[6].node, "Rem");
+	let DiagnosticKind::UnknownToken { literal, position, .. } = &diagnostics[6].kind else {
+		panic!("Expected an unknown token, got {:?}", diagnostics[6].kind)
+	};
+	assert_eq!(literal, "%");
+	assert_eq!(*position, Some(Position { line: 9, column: 19 }));
+
+	assert!(!output_path.join("ast.rs").exists());
+}
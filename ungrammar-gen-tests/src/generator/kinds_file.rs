@@ -0,0 +1,75 @@
+// External Uses
+use ungrammar_gen::generator::KindsMeta;
+use ungrammar_extra::{Assoc, Fixity, Operator, TokenCategory};
+
+
+
+#[test]
+fn kinds_file_is_read() {
+	let meta = KindsMeta::from_toml(indoc::indoc! {r#"
+		token_kind = "crate::lexer::TokenKind"
+
+		[[kind]]
+		lit = "+"
+		name = "PlusOp"
+		desc = "Plus"
+
+		[[kind]]
+		lit = "whitespace"
+		name = "Whitespace"
+		trivia = true
+	"#}).unwrap();
+	assert_eq!(meta.token_kind.as_deref(), Some("crate::lexer::TokenKind"));
+
+	let plus = meta.get("+").unwrap();
+	assert_eq!((plus.name.as_str(), plus.description.as_str()), ("PlusOp", "Plus"));
+	assert!(!plus.trivia);
+
+	let whitespace = meta.get("whitespace").unwrap();
+	assert_eq!(whitespace.description, "Whitespace");
+	assert!(whitespace.trivia);
+
+	let error = KindsMeta::from_toml(indoc::indoc! {r#"
+		[[kind]]
+		lit = "+"
+	"#}).err().unwrap();
+	assert!(error.to_string().contains("missing field `name`"), "{error}");
+}
+
+#[test]
+fn kinds_file_categories_are_validated() {
+	let meta = KindsMeta::from_toml(indoc::indoc! {r#"
+		[[kind]]
+		lit = "fn"
+		name = "FnKw"
+		category = "keyword"
+	"#}).unwrap();
+	assert_eq!(meta.get("fn").unwrap().category, Some(TokenCategory::Keyword));
+
+	let error = KindsMeta::from_toml(indoc::indoc! {r#"
+		[[kind]]
+		lit = "fn"
+		name = "FnKw"
+		category = "keywords"
+	"#}).err().unwrap();
+	assert!(error.to_string().contains("unknown category 'keywords'"), "{error}");
+}
+
+#[test]
+fn kinds_file_operators_are_validated() {
+	let meta = KindsMeta::from_toml(indoc::indoc! {r#"
+		[[kind]]
+		lit = "^"
+		name = "Caret"
+		infix = { prec = 20, assoc = "right" }
+	"#}).unwrap();
+	assert_eq!(meta.get("^").unwrap().operators, [Operator::new(Fixity::Infix(Assoc::Right), 20)]);
+
+	let error = KindsMeta::from_toml(indoc::indoc! {r#"
+		[[kind]]
+		lit = "^"
+		name = "Caret"
+		infix = { prec = 20, assoc = "up" }
+	"#}).err().unwrap();
+	assert!(format!("{error:#}").contains("Unknown associativity 'up'"), "{error:#}");
+}
@@ -0,0 +1,38 @@
+mod calculator {
+	ungrammar_extra_derive::ast!("_data_/calculator.ungram", super::super::TokenKind);
+}
+
+mod helper_names {
+	ungrammar_extra_derive::ast!("_data_/helper_names.ungram", super::super::TokenKind);
+}
+
+
+#[test]
+fn make_builds_typed_nodes_from_their_fields() {
+	use calculator::{ast::{InfixOperator, Literal}, make, AstElement, AstToken};
+
+	let expr = make::expr(
+		Literal::IntLit(make::int_lit("1")),
+		InfixOperator::PlusOp(make::plus_op("+")),
+		Literal::FloatLit(make::float_lit("2.5")),
+	).unwrap();
+
+	let Some(Literal::IntLit(lhs)) = expr.lhs() else { panic!("expected an integer, got {:?}", expr.lhs()) };
+	let Some(InfixOperator::PlusOp(op)) = expr.op() else { panic!("expected a plus, got {:?}", expr.op()) };
+	let Some(Literal::FloatLit(rhs)) = expr.rhs() else { panic!("expected a float, got {:?}", expr.rhs()) };
+
+	assert_eq!([lhs.text(), op.text(), rhs.text()], [Some("1"), Some("+"), Some("2.5")]);
+	assert_eq!(u32::from(expr.span().len()), 5);
+}
+
+#[test]
+fn nodes_can_be_named_like_the_make_helpers() {
+	use helper_names::{ast::Literal, make, printer::ToSource};
+
+	let node = make::node(
+		make::push(Literal::IntLit(make::int_lit("1"))).unwrap(),
+		make::plus_op("+"),
+		make::tree(Literal::IntLit(make::int_lit("2")), make::minus_op("-")).unwrap(),
+	).unwrap();
+	assert_eq!(node.to_source(), "1 + 2 -");
+}
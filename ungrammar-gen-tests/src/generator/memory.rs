@@ -0,0 +1,31 @@
+// Standard Uses
+use std::path::Path;
+
+// External Uses
+use ungrammar_gen::generator;
+
+
+
+#[test]
+fn generate_calculator_to_memory() {
+	let grammar_path = Path::new("_data_/calculator.ungram");
+
+	let output_path = Path::new("generated/");
+
+	let files = generator::to_memory::<super::SyntaxKind, super::TokenKind>(grammar_path, output_path).unwrap();
+
+	let names: Vec<_> = files.keys().map(String::as_str).collect();
+	assert_eq!(names, ["ast.rs", "blanket_impls.rs", "kind.rs", "make.rs", "mod.rs", "printer.rs", "token.rs", "visitor.rs"]);
+	assert!(files["ast.rs"].contains("pub struct Expr(SyntaxNode);"), "{}", files["ast.rs"]);
+	assert!(files["kind.rs"].starts_with("// This file is generated, do not edit"), "{}", files["kind.rs"]);
+}
+
+#[test]
+fn memory_output_uses_the_output_directory_rustfmt_config() {
+	let grammar_path = Path::new("_data_/calculator.ungram");
+	let output_dir = super::TempDir::new("memory");
+	std::fs::write(output_dir.path().join("rustfmt.toml"), "hard_tabs = true\n").unwrap();
+
+	let files = generator::to_memory::<super::SyntaxKind, super::TokenKind>(grammar_path, output_dir.path()).unwrap();
+	assert!(files["ast.rs"].contains("\n\tfn "), "{}", files["ast.rs"]);
+}
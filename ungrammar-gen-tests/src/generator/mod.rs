@@ -1,17 +1,80 @@
 #[cfg(test)]
 pub mod calculator;
+#[cfg(test)]
+pub mod invalid;
+#[cfg(test)]
+pub mod check;
+#[cfg(test)]
+pub mod rowan;
+#[cfg(test)]
+pub mod options;
+#[cfg(test)]
+pub mod memory;
+#[cfg(test)]
+pub mod build;
+#[cfg(test)]
+pub mod ast_macro;
+#[cfg(test)]
+pub mod visitor;
+#[cfg(test)]
+pub mod make;
+#[cfg(test)]
+pub mod printer;
+#[cfg(test)]
+pub mod trivia;
+#[cfg(test)]
+pub mod categories;
+#[cfg(test)]
+pub mod operators;
+#[cfg(test)]
+pub mod inline_enums;
+#[cfg(test)]
+pub mod annotated;
+#[cfg(test)]
+pub mod kinds_file;
+#[cfg(test)]
+pub mod repeats;
+#[cfg(test)]
+pub mod optional;
+#[cfg(test)]
+pub mod separated;
+#[cfg(test)]
+pub mod parser;
+mod token_kind;
+
+pub use token_kind::*;
 
 // Standard Uses
 use std::{io::Write, path::{Path, PathBuf}};
 
 // External Uses
 use once_cell::sync::Lazy;
-use ungrammar_extra_derive::SyntaxKind;
 
 
 pub static GENERATED_PATH: Lazy<PathBuf> = Lazy::new(|| Path::new("generated/").into());
 pub static GENERATED_CODE_PATH: Lazy<PathBuf> = Lazy::new(|| Path::new("generated/src/").into());
 
+/// An empty directory in the system's temporary one, for the tests that write files outside of
+/// [`GENERATED_PATH`], it's removed once dropped, even when the test panics
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+	/// `name` keeps apart the directories of the tests that run at the same time
+	pub fn new(name: &str) -> Self {
+		let path = std::env::temp_dir().join(format!("ungrammar-gen-{name}-{}", std::process::id()));
+		std::fs::remove_dir_all(&path).ok();
+		std::fs::create_dir_all(&path).unwrap();
+
+		Self(path)
+	}
+
+	pub fn path(&self) -> &Path { &self.0 }
+}
+
+impl Drop for TempDir {
+	fn drop(&mut self) { std::fs::remove_dir_all(&self.0).ok(); }
+}
+
 
 
 pub fn setup_generation() {
@@ -47,14 +110,6 @@ fn generate_crate() {
 		ungrammar-extra = { path="../../ungrammar-extra" }
 		cstree = { version = "0.12", features = ["derive", "multi_threaded_interning"] }
 
-		[dependencies.text]
-		git = "https://github.com/SparkyPotato/yam"
-		rev = "ff948ab"
-
-		[dependencies.diagnostics]
-		git = "https://github.com/SparkyPotato/yam"
-		rev = "ff948ab"
-
 		[workspace]
 		"#}
 	).unwrap()
@@ -68,22 +123,3 @@ fn clean_generated_dir() {
     std::fs::create_dir_all(&*GENERATED_CODE_PATH).unwrap();
     std::fs::write(GENERATED_CODE_PATH.join("lib.rs"), "").unwrap();
 }
-
-
-#[derive(SyntaxKind)]
-pub enum TokenKind {
-	#[syntax(lit="string", desc="String")]
-	StringLit,
-
-	#[syntax(lit="+", desc="Plus")]
-	PlusOp,
-	
-	#[syntax(lit="-", desc="Minus")]
-	MinusOp,
-
-	#[syntax(lit="int", desc="Integer")]
-	IntLit,
-	
-	#[syntax(lit="float", desc="Float")]
-	FloatLit,
-}
@@ -0,0 +1,50 @@
+// External Uses
+use ungrammar_extra::{Assoc, Fixity, KindsMetaInfo, Operator};
+
+
+
+#[test]
+fn kinds_meta_info_knows_the_operators() {
+	let infix = Operator::new(Fixity::Infix(Assoc::Left), 10);
+	let prefix = Operator::new(Fixity::Prefix, 30);
+
+	assert_eq!(super::annotated::SyntaxKind::operators_of("+"), [infix]);
+	assert_eq!(super::annotated::SyntaxKind::operators_of("-"), [infix, prefix]);
+	assert_eq!(super::annotated::SyntaxKind::operators_of("int"), []);
+}
+
+#[test]
+fn binding_powers_drive_a_pratt_parser() {
+	use super::annotated::calculator::kind::SyntaxKind;
+
+	assert_eq!(SyntaxKind::PlusOp.infix_binding_power(), Some((10, 11)));
+	assert_eq!(SyntaxKind::MinusOp.prefix_binding_power(), Some(30));
+	assert_eq!(SyntaxKind::PlusOp.prefix_binding_power(), None);
+	assert_eq!(SyntaxKind::IntLit.infix_binding_power(), None);
+	assert_eq!(SyntaxKind::MinusOp.postfix_binding_power(), None);
+
+	fn expr(tokens: &mut std::iter::Peekable<std::slice::Iter<(SyntaxKind, &str)>>, min_bp: u8) -> String {
+		let mut lhs = match tokens.next().unwrap() {
+			(kind, _) if kind.prefix_binding_power().is_some() => {
+				format!("(-{})", expr(tokens, kind.prefix_binding_power().unwrap()))
+			},
+			(_, text) => text.to_string(),
+		};
+
+		while let Some((op, text)) = tokens.peek() {
+			let Some((l_bp, r_bp)) = op.infix_binding_power() else { break };
+			if l_bp < min_bp { break }
+
+			tokens.next();
+			lhs = format!("({lhs} {text} {})", expr(tokens, r_bp));
+		}
+
+		lhs
+	}
+
+	let tokens = [
+		(SyntaxKind::MinusOp, "-"), (SyntaxKind::IntLit, "1"), (SyntaxKind::PlusOp, "+"),
+		(SyntaxKind::IntLit, "2"), (SyntaxKind::MinusOp, "-"), (SyntaxKind::IntLit, "3"),
+	];
+	assert_eq!(expr(&mut tokens.iter().peekable(), 0), "(((-1) + 2) - 3)");
+}
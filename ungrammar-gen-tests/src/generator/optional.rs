@@ -0,0 +1,72 @@
+// Standard Uses
+use std::path::Path;
+
+// External Uses
+use ungrammar_gen::generator::{self, Diagnostic, DiagnosticKind, GenError};
+
+
+
+mod optional_fields {
+	ungrammar_extra_derive::ast!("_data_/optional.ungram", super::super::TokenKind);
+}
+
+
+#[test]
+fn required_fields_have_expect_accessors() {
+	use optional_fields::{ast::*, make, printer::ToSource, AstToken};
+
+	let sum = make::sum(Literal::IntLit(make::int_lit("1")), make::plus_op("+"), None).unwrap();
+
+	assert!(matches!(sum.expect_lhs(), Literal::IntLit(_)));
+	assert_eq!(sum.expect_plus_op().text(), Some("+"));
+	assert!(sum.rhs().is_none());
+	assert_eq!(sum.to_source(), "1 +");
+
+	let sum = make::sum(Literal::IntLit(make::int_lit("1")), make::plus_op("+"), Some(Literal::IntLit(make::int_lit("2")))).unwrap();
+	assert_eq!(sum.to_source(), "1 + 2");
+}
+
+#[test]
+fn optional_fields_have_no_expect_accessor() {
+	let grammar_path = Path::new("_data_/optional.ungram");
+
+	let files = generator::to_memory::<super::SyntaxKind, super::TokenKind>(grammar_path, Path::new("generated/")).unwrap();
+
+	assert!(files["ast.rs"].contains("pub fn expect_lhs"), "{}", files["ast.rs"]);
+	assert!(!files["ast.rs"].contains("pub fn expect_rhs"), "{}", files["ast.rs"]);
+	// The subtrahend is found after the `-` whether the minuend is there or not
+	assert!(files["ast.rs"].contains("pub fn expect_subtrahend"), "{}", files["ast.rs"]);
+	assert!(!files["ast.rs"].contains("pub fn expect_minuend"), "{}", files["ast.rs"]);
+}
+
+#[test]
+fn optional_fields_are_told_apart_from_others_of_their_type() {
+	use optional_fields::{ast::*, make, printer::ToSource, AstToken};
+
+	let text = |literal: Option<Literal>| match literal {
+		Some(Literal::IntLit(x)) => x.text().map(str::to_owned),
+		other => panic!("expected an integer, got {other:?}"),
+	};
+
+	let diff = make::diff(None, make::minus_op("-"), Literal::IntLit(make::int_lit("2"))).unwrap();
+	assert!(diff.minuend().is_none(), "{:?}", diff.minuend());
+	assert_eq!(text(Some(diff.expect_subtrahend())), Some("2".to_owned()));
+	assert_eq!(diff.to_source(), "- 2");
+
+	let diff = make::diff(Some(Literal::IntLit(make::int_lit("1"))), make::minus_op("-"), Literal::IntLit(make::int_lit("2"))).unwrap();
+	assert_eq!(text(diff.minuend()), Some("1".to_owned()));
+	assert_eq!(text(diff.subtrahend()), Some("2".to_owned()));
+	assert_eq!(diff.to_source(), "1 - 2");
+}
+
+#[test]
+fn optional_fields_without_a_token_in_between_are_rejected() {
+	let grammar_dir = super::TempDir::new("optional");
+	let grammar_path = grammar_dir.path().join("ambiguous.ungram");
+	std::fs::write(&grammar_path, "Pair = first:Literal? second:Literal? '+'\nLiteral = 'int' | 'float' | 'string'").unwrap();
+
+	let result = generator::to_memory::<super::SyntaxKind, super::TokenKind>(&grammar_path, Path::new("generated/"));
+
+	let Err(GenError::Lowering(diagnostics)) = &result else { panic!("expected lowering errors, got {:?}", result.map(|_| ())) };
+	assert!(matches!(&diagnostics[..], [Diagnostic { kind: DiagnosticKind::AmbiguousOptional { ty }, .. }] if ty == "Literal"), "{diagnostics:?}");
+}
@@ -0,0 +1,170 @@
+// Standard Uses
+use std::path::Path;
+
+// External Uses
+use ungrammar_gen::generator::{self, CustomType, GenError, GeneratorOptions, HandwrittenNode, KindsMeta, NodeHook};
+
+
+pub mod handwritten {
+	include!(concat!(env!("OUT_DIR"), "/handwritten.rs"));
+
+	/// Takes the place of the generated node, so it implements what the generated code uses
+	#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+	pub struct Group(SyntaxNode);
+
+	impl AstElement for Group {
+		fn can_cast(kind: SyntaxKind) -> bool { kind == SyntaxKind::Group }
+
+		fn cast(elem: SyntaxElement) -> Option<Self> {
+			let node = elem.into_node()?;
+			Self::can_cast(node.kind()).then_some(Self(node))
+		}
+
+		fn span(&self) -> Span { blanket_impls::node_span(&self.0) }
+
+		fn inner(self) -> SyntaxElement { self.0.into() }
+	}
+
+	impl printer::ToSource for Group {
+		fn print(&self, printer: &mut printer::Printer) {
+			printer.token(SyntaxKind::PlusOp, "+");
+			children::<ast::Literal>(&self.0).for_each(|literal| literal.print(printer));
+		}
+	}
+}
+
+fn generate(options: &GeneratorOptions, name: &str) -> Result<[String; 3], GenError> {
+	let grammar_path = Path::new("_data_/calculator.ungram");
+	let output_dir = super::TempDir::new(name);
+
+	generator::from_path_with_meta(
+		grammar_path, output_dir.path(),
+		&KindsMeta::from_info::<super::SyntaxKind>(), core::any::type_name::<super::TokenKind>(), options
+	).map(|_| ["mod.rs", "token.rs", "blanket_impls.rs"].map(|file| {
+		std::fs::read_to_string(output_dir.path().join(file)).unwrap()
+	}))
+}
+
+#[test]
+fn default_span_and_text_types() {
+	let [traits, tokens, _] = generate(&GeneratorOptions::default(), "default-types").unwrap();
+
+	assert!(traits.contains("pub type Span = ungrammar_extra::TextRange;"), "{traits}");
+	assert!(traits.contains("fn text(&self) -> Option<&str>;"), "{traits}");
+	assert!(tokens.contains("fn text(&self) -> Option<&str>"), "{tokens}");
+}
+
+#[test]
+fn custom_span_and_text_types() {
+	let options = GeneratorOptions {
+		span: Some(CustomType { path: "crate::FileSpan".into(), convert: "crate::file_span".into() }),
+		text: Some(CustomType { path: "crate::Text".into(), convert: "crate::token_text".into() }),
+		..Default::default()
+	};
+	let [traits, tokens, blanket_impls] = generate(&options, "custom-types").unwrap();
+
+	assert!(traits.contains("pub type Span = crate::FileSpan;"), "{traits}");
+	assert!(traits.contains("pub type Text = crate::Text;"), "{traits}");
+	assert!(tokens.contains("crate::token_text(&self.0)"), "{tokens}");
+	assert!(blanket_impls.contains("crate::file_span(token.text_range())"), "{blanket_impls}");
+}
+
+#[test]
+fn invalid_custom_type_is_rejected() {
+	let options = GeneratorOptions {
+		span: Some(CustomType { path: "crate::FileSpan".into(), convert: "not a path".into() }),
+		..Default::default()
+	};
+
+	let result = generate(&options, "invalid-types");
+	assert!(matches!(result, Err(GenError::InvalidOption { option: "span", .. })), "{result:?}");
+}
+
+fn generate_ast(options: &GeneratorOptions) -> Result<String, GenError> {
+	let grammar_path = Path::new("_data_/calculator.ungram");
+
+	generator::to_memory_with_meta(
+		grammar_path, Path::new("generated/"), &KindsMeta::from_info::<super::SyntaxKind>(), core::any::type_name::<super::TokenKind>(), options
+	).map(|mut files| files.remove("ast.rs").unwrap())
+}
+
+#[test]
+fn handwritten_nodes_are_replaced_and_hooks_added() {
+	let options = GeneratorOptions {
+		handwritten: vec![HandwrittenNode {
+			node: "InfixOperator".into(),
+			replacement: Some("pub type InfixOperator = PlusOp;".into()),
+		}],
+		node_hooks: vec![NodeHook {
+			node: "Expr".into(),
+			items: "impl Expr { pub fn is_binary(&self) -> bool { true } }".into(),
+		}],
+		..Default::default()
+	};
+	let ast = generate_ast(&options).unwrap();
+
+	assert!(!ast.contains("pub enum InfixOperator"), "{ast}");
+	assert!(ast.contains("pub type InfixOperator = PlusOp;"), "{ast}");
+	assert!(ast.contains("pub fn is_binary(&self) -> bool"), "{ast}");
+}
+
+#[test]
+fn handwritten_nodes_compile_with_the_generated_code() {
+	use cstree::build::GreenNodeBuilder;
+	use handwritten::{ast::Stmt, kind::SyntaxKind, make, printer::ToSource, AstElement, Group, SyntaxNode};
+
+	let mut builder = GreenNodeBuilder::<SyntaxKind>::new();
+	builder.start_node(SyntaxKind::Group);
+	builder.token(SyntaxKind::PlusOp, "+");
+	builder.token(SyntaxKind::IntLit, "1");
+	builder.finish_node();
+	let (green, cache) = builder.finish();
+
+	let interner = cache.and_then(|cache| cache.into_interner()).unwrap();
+	let root = SyntaxNode::new_root_with_resolver(green, interner);
+	let group = Group::cast(root.syntax().clone().into()).unwrap();
+
+	// Read, built and printed by the generated code, as a field and as an enum variant
+	let neg = make::neg(make::minus_op("-"), group.clone()).unwrap();
+	assert_eq!(neg.expect_group().to_source(), "+ 1");
+	assert_eq!(neg.to_source(), "- + 1");
+
+	let stmt = Stmt::cast(group.inner()).unwrap();
+	assert!(matches!(stmt, Stmt::Group(_)), "{stmt:?}");
+	assert_eq!(stmt.to_source(), "+ 1");
+	assert_eq!(Stmt::Neg(neg).to_source(), "- + 1");
+}
+
+#[test]
+fn make_rejects_children_of_trees_without_a_resolver() {
+	use cstree::build::GreenNodeBuilder;
+	use handwritten::{kind::SyntaxKind, make, AstElement, Group, SyntaxNode};
+
+	let mut builder = GreenNodeBuilder::<SyntaxKind>::new();
+	builder.start_node(SyntaxKind::Group);
+	builder.token(SyntaxKind::PlusOp, "+");
+	builder.token(SyntaxKind::IntLit, "1");
+	builder.finish_node();
+	let (green, _) = builder.finish();
+
+	let group = Group::cast(SyntaxNode::new_root(green).into()).unwrap();
+	let result = make::neg(make::minus_op("-"), group);
+	assert!(matches!(result, Err(make::MakeError { kind: SyntaxKind::PlusOp })), "{result:?}");
+}
+
+#[test]
+fn invalid_handwritten_nodes_and_hooks_are_rejected() {
+	let unknown_node = GeneratorOptions {
+		node_hooks: vec![NodeHook { node: "Statement".into(), items: "impl Statement {}".into() }],
+		..Default::default()
+	};
+	let result = generate_ast(&unknown_node);
+	assert!(matches!(&result, Err(GenError::InvalidOption { option: "node hook", value }) if value == "Statement"), "{result:?}");
+
+	let invalid_items = GeneratorOptions {
+		handwritten: vec![HandwrittenNode { node: "Literal".into(), replacement: Some("pub enum Literal {".into()) }],
+		..Default::default()
+	};
+	let result = generate_ast(&invalid_items);
+	assert!(matches!(result, Err(GenError::InvalidOption { option: "handwritten", .. })), "{result:?}");
+}
@@ -0,0 +1,49 @@
+// Crate Uses
+use crate::generator::annotated::calculator::{ast::*, kind::SyntaxKind, AstElement, AstToken};
+
+// External Uses
+use ungrammar_extra::parser::{Parse, Parser};
+
+
+/// `Expr = lhs:Literal op:InfixOperator rhs:Literal`, written against the runtime of `ungrammar_extra::parser`
+fn parse(tokens: &[(SyntaxKind, &str)]) -> Parse<SyntaxKind> {
+	let mut p = Parser::new(tokens);
+	let expr = p.start();
+	p.expect(SyntaxKind::IntLit);
+	match p.at_any(&[SyntaxKind::PlusOp, SyntaxKind::MinusOp]) {
+		true => p.bump(),
+		false => p.error(&[SyntaxKind::PlusOp, SyntaxKind::MinusOp]),
+	}
+	p.expect(SyntaxKind::IntLit);
+	expr.complete(&mut p, SyntaxKind::Expr);
+
+	p.finish()
+}
+
+
+#[test]
+fn the_generated_kind_works_with_the_parser_runtime() {
+	use SyntaxKind::{Comment, IntLit, PlusOp, Whitespace};
+
+	let parse = parse(&[(IntLit, "1"), (Whitespace, " "), (PlusOp, "+"), (Comment, "/* two */"), (IntLit, "2")]);
+	assert!(parse.errors.is_empty(), "{:?}", parse.errors);
+	assert_eq!(parse.root.to_string(), "1 +/* two */2");
+
+	let expr = Expr::cast(parse.root.syntax().clone().into()).unwrap();
+	assert!(matches!(expr.op(), Some(InfixOperator::PlusOp(_))));
+	let Some(Literal::IntLit(rhs)) = expr.rhs() else { panic!("expected an integer, got {:?}", expr.rhs()) };
+	assert_eq!(rhs.text(), Some("2"));
+}
+
+#[test]
+fn parse_errors_use_the_kind_descriptions() {
+	use SyntaxKind::*;
+
+	let errors = parse(&[(IntLit, "1"), (Whitespace, " "), (IntLit, "2")]).errors;
+	let errors: Vec<_> = errors.iter().map(|e| (e.position, e.to_string())).collect();
+	assert_eq!(errors, [(2, "Expected Plus or Minus, found Integer".to_owned())]);
+
+	let errors = parse(&[(IntLit, "1"), (PlusOp, "+"), (Whitespace, " ")]).errors;
+	let errors: Vec<_> = errors.iter().map(|e| (e.position, e.to_string())).collect();
+	assert_eq!(errors, [(3, "Expected Integer, found the end of input".to_owned())]);
+}
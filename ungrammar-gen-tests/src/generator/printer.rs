@@ -0,0 +1,70 @@
+// External Uses
+use cstree::build::GreenNodeBuilder;
+
+
+
+mod calculator {
+	ungrammar_extra_derive::ast!("_data_/calculator.ungram", super::super::TokenKind);
+}
+
+// Missing tokens are only filled in for keywords and punctuation, so this takes the kinds with categories
+mod lists {
+	ungrammar_extra_derive::ast!("_data_/lists.ungram", super::super::annotated::TokenKind);
+}
+
+
+#[test]
+fn to_source_prints_tokens_in_grammar_order() {
+	use calculator::{ast::{InfixOperator, Literal}, kind::SyntaxKind, make, printer::{Spacing, ToSource}};
+
+	let expr = make::expr(
+		Literal::IntLit(make::int_lit("1")),
+		InfixOperator::MinusOp(make::minus_op("-")),
+		Literal::FloatLit(make::float_lit("2.5")),
+	).unwrap();
+
+	assert_eq!(expr.to_source(), "1 - 2.5");
+	assert_eq!(expr.to_source_with(Spacing::Compact), "1-2.5");
+	assert_eq!(
+		expr.to_source_with(Spacing::Custom(|_, next| next != SyntaxKind::MinusOp)),
+		"1- 2.5"
+	);
+}
+
+#[test]
+fn to_source_separates_lists_and_fills_in_missing_tokens() {
+	use lists::{ast::{Neg, Pos, Sum}, kind::SyntaxKind, printer::ToSource, AstElement, SyntaxNode};
+
+	let mut builder = GreenNodeBuilder::<SyntaxKind>::new();
+	builder.start_node(SyntaxKind::Eof);
+	builder.start_node(SyntaxKind::Sum);
+	builder.token(SyntaxKind::IntLit, "1");
+	builder.token(SyntaxKind::PlusOp, "+");
+	builder.token(SyntaxKind::IntLit, "2");
+	// The separator is missing, so its literal is printed instead (it's punctuation), the trailing one is kept
+	builder.token(SyntaxKind::FloatLit, "3.0");
+	builder.token(SyntaxKind::PlusOp, "+");
+	builder.finish_node();
+	// The minus is missing, so its literal is printed instead
+	builder.start_node(SyntaxKind::Neg);
+	builder.token(SyntaxKind::IntLit, "4");
+	builder.finish_node();
+	// The integer is missing, its literal is only the name of the kind, so nothing is printed
+	builder.start_node(SyntaxKind::Pos);
+	builder.token(SyntaxKind::PlusOp, "+");
+	builder.finish_node();
+	builder.finish_node();
+	let (green, cache) = builder.finish();
+
+	let interner = cache.and_then(|cache| cache.into_interner()).unwrap();
+	let root = SyntaxNode::new_root_with_resolver(green, interner);
+	let mut children = root.syntax().children().cloned();
+
+	let sum = Sum::cast(children.next().unwrap().into()).unwrap();
+	let neg = Neg::cast(children.next().unwrap().into()).unwrap();
+	let pos = Pos::cast(children.next().unwrap().into()).unwrap();
+
+	assert_eq!(sum.to_source(), "1 + 2 + 3.0 +");
+	assert_eq!(neg.to_source(), "- 4");
+	assert_eq!(pos.to_source(), "+");
+}
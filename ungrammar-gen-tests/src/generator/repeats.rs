@@ -0,0 +1,33 @@
+mod repeated {
+	ungrammar_extra_derive::ast!("_data_/repeats.ungram", super::super::TokenKind);
+}
+
+
+#[test]
+fn repeated_tokens_become_many_fields() {
+	use repeated::{ast::*, make, printer::ToSource, AstToken};
+
+	let signs = make::signs([make::minus_op("-"), make::minus_op("-")], Literal::IntLit(make::int_lit("1"))).unwrap();
+
+	let minuses: Vec<_> = signs.minus_ops().map(|op| op.text().unwrap().to_string()).collect();
+	assert_eq!(minuses, ["-", "-"]);
+	assert!(matches!(signs.value(), Some(Literal::IntLit(_))));
+	assert_eq!(signs.to_source(), "- - 1");
+}
+
+#[test]
+fn repeated_sequences_become_tuples() {
+	use repeated::{ast::*, make, printer::ToSource, AstToken};
+
+	let sum = make::sum(
+		Literal::IntLit(make::int_lit("1")),
+		[
+			(make::plus_op("+"), Literal::IntLit(make::int_lit("2"))),
+			(make::plus_op("+"), Literal::FloatLit(make::float_lit("3.5"))),
+		],
+	).unwrap();
+
+	let rest: Vec<_> = sum.rest().map(|(op, literal)| (op.text().unwrap().to_string(), literal.to_source())).collect();
+	assert_eq!(rest, [("+".to_string(), "2".to_string()), ("+".to_string(), "3.5".to_string())]);
+	assert_eq!(sum.to_source(), "1 + 2 + 3.5");
+}
@@ -0,0 +1,31 @@
+// Standard Uses
+use std::path::Path;
+
+// External Uses
+use ungrammar_gen::generator::{self, Backend, GeneratorOptions, KindsMeta};
+
+
+
+#[test]
+fn generate_calculator_for_rowan() {
+	let grammar_path = Path::new("_data_/calculator.ungram");
+	// Kept out of the generated crate, since it only depends on cstree
+	let output_dir = super::TempDir::new("rowan");
+	let output_path = output_dir.path();
+
+	generator::from_path_with_meta(
+		grammar_path, output_path,
+		&KindsMeta::from_info::<super::SyntaxKind>(), core::any::type_name::<super::TokenKind>(),
+		&GeneratorOptions { backend: Backend::Rowan, ..Default::default() }
+	).unwrap();
+
+	let kind = std::fs::read_to_string(output_path.join("kind.rs")).unwrap();
+	let traits = std::fs::read_to_string(output_path.join("mod.rs")).unwrap();
+	let tokens = std::fs::read_to_string(output_path.join("token.rs")).unwrap();
+
+	assert!(kind.contains("impl rowan::Language for Lang"), "{kind}");
+	assert!(traits.contains("rowan::SyntaxNode<Lang>"), "{traits}");
+	for file in [kind, traits, tokens] {
+		assert!(!file.contains("cstree"), "{file}");
+	}
+}
@@ -0,0 +1,51 @@
+mod separated_lists {
+	ungrammar_extra_derive::ast!("_data_/separated.ungram", super::super::TokenKind);
+}
+
+
+#[test]
+fn separated_lists_keep_their_separators() {
+	use separated_lists::{ast::*, make, printer::ToSource, AstToken};
+
+	let sum = make::sum(Literal::IntLit(make::int_lit("1")), [Literal::FloatLit(make::float_lit("2.5"))]).unwrap();
+	let list = sum.literals();
+
+	let pairs: Vec<_> = list.pairs().map(|(x, separator)| (x.to_source(), separator.and_then(|s| s.text().map(str::to_string)))).collect();
+	assert_eq!(pairs, [("1".to_string(), Some("+".to_string())), ("2.5".to_string(), None)]);
+	assert_eq!(list.elements().count(), 2);
+	assert_eq!(list.separators().count(), 1);
+	assert!(!list.has_trailing_separator());
+	assert_eq!(sum.expect_first_literals().to_source(), "1");
+	assert_eq!(sum.to_source(), "1 + 2.5");
+}
+
+#[test]
+fn separators_can_be_any_token() {
+	use separated_lists::{ast::*, make, printer::ToSource};
+
+	let diff = make::diff([Literal::IntLit(make::int_lit("1")), Literal::IntLit(make::int_lit("2"))]).unwrap();
+	assert_eq!(diff.to_source(), "1 - 2");
+
+	let empty = make::diff([]).unwrap();
+	assert!(empty.terms().is_empty());
+	assert!(empty.terms().first().is_none());
+	assert_eq!(empty.to_source(), "");
+}
+
+#[test]
+fn separated_lists_can_be_in_between_other_rules() {
+	use separated_lists::{ast::*, make, printer::ToSource, AstToken};
+
+	let group = make::group(
+		make::plus_op("+"),
+		Literal::IntLit(make::int_lit("1")),
+		[Literal::IntLit(make::int_lit("2"))],
+		make::plus_op("+"),
+	).unwrap();
+
+	assert_eq!(group.literals().elements().count(), 2);
+	assert_eq!(group.literals().separators().count(), 1);
+	assert_eq!(group.expect_first_literals().to_source(), "1");
+	assert_eq!(group.expect_close().text(), Some("+"));
+	assert_eq!(group.to_source(), "+ 1 - 2 +");
+}
@@ -0,0 +1,22 @@
+// External Uses
+use ungrammar_extra_derive::SyntaxKind;
+
+
+/// Token kinds shared by the test grammars, the build script also generates from them
+#[derive(SyntaxKind)]
+pub enum TokenKind {
+	#[syntax(lit="string", desc="String")]
+	StringLit,
+
+	#[syntax(lit="+", desc="Plus")]
+	PlusOp,
+	
+	#[syntax(lit="-", desc="Minus")]
+	MinusOp,
+
+	#[syntax(lit="int", desc="Integer")]
+	IntLit,
+	
+	#[syntax(lit="float", desc="Float")]
+	FloatLit,
+}
@@ -0,0 +1,49 @@
+// External Uses
+use cstree::build::GreenNodeBuilder;
+
+
+
+#[test]
+fn trivia_kinds_are_generated_even_if_the_grammar_does_not_use_them() {
+	use super::annotated::calculator::kind::SyntaxKind;
+
+	assert!(SyntaxKind::Whitespace.is_trivia());
+	assert!(SyntaxKind::Comment.is_trivia());
+	assert!(!SyntaxKind::Error.is_trivia());
+	assert!(SyntaxKind::Error.is_error());
+	assert!(!SyntaxKind::IntLit.is_trivia());
+	assert_eq!(SyntaxKind::Whitespace.to_string(), "whitespace");
+}
+
+#[test]
+fn trivia_in_the_tree_is_skipped_by_accessors() {
+	use super::annotated::calculator::{ast::*, kind::SyntaxKind, AstElement, AstToken, SyntaxNode};
+
+	let mut builder = GreenNodeBuilder::<SyntaxKind>::new();
+	builder.start_node(SyntaxKind::Expr);
+	builder.token(SyntaxKind::IntLit, "1");
+	builder.token(SyntaxKind::Whitespace, " ");
+	builder.token(SyntaxKind::PlusOp, "+");
+	builder.token(SyntaxKind::Comment, "/* two */");
+	builder.token(SyntaxKind::IntLit, "2");
+	builder.finish_node();
+	let (green, cache) = builder.finish();
+
+	let interner = cache.and_then(|cache| cache.into_interner()).unwrap();
+	let root = SyntaxNode::new_root_with_resolver(green, interner);
+	let expr = Expr::cast(root.syntax().clone().into()).unwrap();
+
+	assert!(matches!(expr.op(), Some(InfixOperator::PlusOp(_))));
+	let Some(Literal::IntLit(rhs)) = expr.rhs() else { panic!("expected an integer, got {:?}", expr.rhs()) };
+	assert_eq!(rhs.text(), Some("2"));
+}
+
+#[test]
+fn kinds_meta_info_knows_the_trivia() {
+	use ungrammar_extra::KindsMetaInfo;
+
+	assert!(super::annotated::SyntaxKind::is_trivia("whitespace"));
+	assert!(super::annotated::SyntaxKind::is_trivia("comment"));
+	assert!(super::annotated::SyntaxKind::is_error("error"));
+	assert!(!super::annotated::SyntaxKind::is_trivia("+"));
+}
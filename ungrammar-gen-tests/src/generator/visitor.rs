@@ -0,0 +1,42 @@
+// External Uses
+use cstree::build::GreenNodeBuilder;
+
+
+
+mod calculator {
+	ungrammar_extra_derive::ast!("_data_/calculator.ungram", super::super::TokenKind);
+}
+
+#[derive(Default)]
+struct Recorder {
+	events: Vec<&'static str>,
+}
+
+impl calculator::visitor::Visitor for Recorder {
+	fn visit_expr(&mut self, _: &calculator::ast::Expr) { self.events.push("visit expr") }
+	fn leave_expr(&mut self, _: &calculator::ast::Expr) { self.events.push("leave expr") }
+	fn visit_literal(&mut self, _: &calculator::ast::Literal) { self.events.push("visit literal") }
+	fn visit_infix_operator(&mut self, _: &calculator::ast::InfixOperator) { self.events.push("visit operator") }
+}
+
+#[test]
+fn walk_visits_every_node_in_order() {
+	use calculator::{ast::Expr, kind::SyntaxKind, visitor, AstElement, SyntaxNode};
+
+	let mut builder = GreenNodeBuilder::<SyntaxKind>::new();
+	builder.start_node(SyntaxKind::Expr);
+	builder.token(SyntaxKind::IntLit, "1");
+	builder.token(SyntaxKind::MinusOp, "-");
+	builder.token(SyntaxKind::StringLit, "\"2\"");
+	builder.finish_node();
+	let (green, _) = builder.finish();
+
+	let expr = Expr::cast(SyntaxNode::new_root(green).into()).unwrap();
+	let mut recorder = Recorder::default();
+	visitor::walk(&expr, &mut recorder);
+
+	assert_eq!(
+		recorder.events,
+		["visit expr", "visit literal", "visit operator", "visit literal", "leave expr"]
+	);
+}
@@ -2,28 +2,50 @@
 use std::path::Path;
 
 // Crate Uses
-use crate::generator::GENERATED_PATH;
+use crate::generator::{SyntaxKind, TempDir};
 
-// External Uses
-use ungrammar_extra_derive::SyntaxKind;
 
 
+/// The AST of the calculator along with its parser, which the build script generates
+mod calculator {
+	ungrammar_extra_derive::ast!("_data_/calculator.ungram", crate::generator::TokenKind);
+	include!(concat!(env!("OUT_DIR"), "/parser_mod.rs"));
+}
 
 #[test]
-fn generate_with_macroed_token_kind_enum() {
-	#[allow(unused)]
-	#[derive(SyntaxKind)]
-	enum TokenKind {
-		#[syntax(lit = "string", desc = "String")]
-		StringLit,
-	}
-
-	let grammar_path = Path::new("_data_/calculator.ungram");
-	let output_path = GENERATED_PATH.join("/generator_new/calculator/");
-
-	if !output_path.exists() {
-		std::fs::create_dir_all(&output_path).unwrap();
-	}
-
-	ungrammar_gen::generator_new::from_path::<SyntaxKind>(grammar_path, &output_path).unwrap();
+fn generate_parser_for_calculator() {
+	let output_dir = TempDir::new("new-calculator");
+
+	ungrammar_gen::generator_new::from_path::<SyntaxKind>(Path::new("_data_/calculator.ungram"), output_dir.path()).unwrap();
+	let parser = std::fs::read_to_string(output_dir.path().join("parser.rs")).unwrap();
+
+	assert!(parser.contains("pub fn parse_expr(tokens: &[(SyntaxKind, &str)])"), "{parser}");
+	// Enum nodes don't start a node of their own, the token they match goes in the struct that uses them
+	assert!(parser.contains("fn literal(p: &mut Parser)") && parser.contains("literal(p);"), "{parser}");
+	assert!(!parser.contains("parse_literal"), "{parser}");
+}
+
+#[test]
+fn left_recursive_grammar_is_rejected() {
+	let output_dir = TempDir::new("new-left-recursive");
+	let grammar_path = output_dir.path().join("left_recursive.ungram");
+	std::fs::write(&grammar_path, "Sum = Sum '+' Literal | Literal\nLiteral = 'int'").unwrap();
+
+	let error = ungrammar_gen::generator_new::from_path::<SyntaxKind>(&grammar_path, output_dir.path()).unwrap_err();
+
+	assert!(error.to_string().contains("'Sum' is left recursive"), "{error}");
+}
+
+#[test]
+fn generated_parser_builds_trees_the_ast_reads() {
+	use calculator::{ast::*, kind::SyntaxKind, parser, AstElement, AstToken};
+
+	let parse = parser::parse_expr(&[(SyntaxKind::IntLit, "1"), (SyntaxKind::PlusOp, "+"), (SyntaxKind::IntLit, "2")]);
+	assert_eq!(parse.errors, []);
+
+	let expr = Expr::cast(parse.root.syntax().clone().into()).unwrap();
+	let (Some(Literal::IntLit(lhs)), Some(InfixOperator::PlusOp(_)), Some(Literal::IntLit(rhs))) = (expr.lhs(), expr.op(), expr.rhs()) else {
+		panic!("Expected `1 + 2`, got {expr:?}")
+	};
+	assert_eq!((lhs.text(), rhs.text()), (Some("1"), Some("2")));
 }
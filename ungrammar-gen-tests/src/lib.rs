@@ -1,4 +1,4 @@
 pub mod generator;
 
-//#[cfg(test)]
-//pub mod generator_new;
+#[cfg(test)]
+pub mod generator_new;
@@ -0,0 +1,69 @@
+// Standard Uses
+use std::path::{Path, PathBuf};
+
+// Crate Uses
+use crate::generator::{self, GenError, Generator, GeneratorOptions, KindsMeta};
+
+// External Uses
+use ungrammar_extra::KindsMetaInfo;
+
+
+
+/// Generates the code of the grammar from a build script into `OUT_DIR`, as a single file named
+/// after the grammar (ex: `calculator.ungram` becomes `calculator.rs`) which can be included with:
+/// ```ignore
+/// mod syntax {
+///     include!(concat!(env!("OUT_DIR"), "/calculator.rs"));
+/// }
+/// ```
+/// Returns the path of the generated file
+pub fn generate<M: KindsMetaInfo + 'static, K>(grammar_path: impl AsRef<Path>) -> Result<PathBuf, GenError> {
+	generate_with_meta(
+		grammar_path, &KindsMeta::from_info::<M>(), core::any::type_name::<K>(), &GeneratorOptions::default()
+	)
+}
+
+/// Same as [`generate`], but with the token kinds information given at runtime
+pub fn generate_with_meta(
+	grammar_path: impl AsRef<Path>, meta: &KindsMeta, token_kind_namespace: &str, options: &GeneratorOptions
+) -> Result<PathBuf, GenError> {
+	let out_dir = std::env::var_os("OUT_DIR").ok_or(GenError::MissingOutDir)?;
+
+	generate_into_with_meta(out_dir, grammar_path, meta, token_kind_namespace, options)
+}
+
+/// Same as [`generate`], but into the given directory instead of `OUT_DIR`
+pub fn generate_into<M: KindsMetaInfo + 'static, K>(
+	out_dir: impl AsRef<Path>, grammar_path: impl AsRef<Path>
+) -> Result<PathBuf, GenError> {
+	generate_into_with_meta(
+		out_dir, grammar_path, &KindsMeta::from_info::<M>(), core::any::type_name::<K>(), &GeneratorOptions::default()
+	)
+}
+
+/// Same as [`generate_into`], but with the token kinds information given at runtime
+pub fn generate_into_with_meta(
+	out_dir: impl AsRef<Path>, grammar_path: impl AsRef<Path>, meta: &KindsMeta, token_kind_namespace: &str,
+	options: &GeneratorOptions
+) -> Result<PathBuf, GenError> {
+	let grammar_path = grammar_path.as_ref();
+
+	println!("cargo:rerun-if-changed={}", grammar_path.display());
+
+	let (file, grammar) = generator::read_grammar(grammar_path)?;
+
+	let name = grammar_path.file_stem().unwrap_or(grammar_path.as_os_str());
+	let path = out_dir.as_ref().join(name).with_extension("rs");
+
+	let text = Generator::new(&grammar, meta, token_kind_namespace.to_owned())
+		.with_source(&file)
+		.with_options(options.clone())
+		.generate_bundle(&path)?;
+
+	// Rewriting a file with the same content would still make cargo rebuild whatever includes it
+	if std::fs::read_to_string(&path).ok().as_deref() != Some(&*text) {
+		std::fs::write(&path, text).map_err(|source| GenError::Write { path: path.clone(), source })?;
+	}
+
+	Ok(path)
+}
@@ -1,70 +1,127 @@
 // Standard Uses
 
 // Crate Uses
-use super::gen::{Enum, Generator, NodeData, Struct};
+use super::gen::{Enum, Generator, NodeData, Span, Struct};
 use crate::generator::gen::{Cardinality, Field, NodeType};
 
 // External Uses
 use quote::{format_ident, quote};
-use ungrammar_extra::KindsMetaInfo;
 
 
 
-pub fn generate_ast<'a, M: KindsMetaInfo + 'static>(generator: &mut Generator<'a, M>) -> String {
-	let nodes = generator.gen_nodes();
-
+pub fn generate_ast(generator: &Generator, nodes: &[NodeData]) -> String {
 	nodes
-		.into_iter()
+		.iter()
 		.map(|node| {
-			match node {
-				NodeData::Struct(s) => generate_struct(generator, s),
-				NodeData::Enum(e) => generate_enum(generator, e),
-			}
-			.to_string()
+			let (name, generated) = match node {
+				NodeData::Struct(s) => (&s.name, generate_struct(generator, s)),
+				NodeData::Enum(e) => (&e.name, generate_enum(generator, e)),
+			};
+			let hooks = generator.options.hooks_of(name);
+
+			quote! { #generated #hooks }.to_string()
 		})
+		.chain(std::iter::once(generator.options.replacements().to_string()))
 		.intersperse("\n\n".to_string())
 		.collect()
 }
 
-fn generate_struct<'a, M: KindsMetaInfo>(_: &mut Generator<'a, M>, s: Struct) -> proc_macro2::TokenStream {
+/// Finds a field of an ambiguous type among the children of its type in its span, when there are
+/// less of them than its fields the optional one is missing, and the ones after it come one earlier
+fn span_child(s: &Struct, span: &Span, ty: &proc_macro2::Ident) -> proc_macro2::TokenStream {
+	let anchor = |index: Option<usize>| index.map(|i| format_ident!("{}", s.fields[i].name()));
+	let (after, before) = (anchor(span.after), anchor(span.before));
+
+	let mut elements = quote! { elements(&self.0) };
+	if after.is_some() {
+		elements = quote! { #elements.skip_while(|x| *x != after).skip(1) };
+	}
+	if before.is_some() {
+		elements = quote! { #elements.take_while(|x| *x != before) };
+	}
+	let after = after.map(|after| quote! { let after = self.#after()?.inner(); });
+	let before = before.map(|before| quote! { let before = self.#before()?.inner(); });
+
+	let (position, required) = (span.position, span.required);
+	let index = match span.optional.as_slice() {
+		[optional] if *optional == position => quote! {
+			if found.len() <= #required { return None }
+			#position
+		},
+		[optional] if *optional < position => {
+			let shifted = position - 1;
+			quote! { if found.len() > #required { #position } else { #shifted } }
+		},
+		_ => quote! { #position },
+	};
+
+	quote! {
+		#after
+		#before
+		let found: Vec<#ty> = #elements.filter_map(#ty::cast).collect();
+		let index = { #index };
+		found.into_iter().nth(index)
+	}
+}
+
+fn generate_struct(_: &Generator, s: &Struct) -> proc_macro2::TokenStream {
 	let name = format_ident!("{}", s.name);
-	let fields = s.fields.into_iter().map(|f| match f {
-		Field::Node { name, ty, cardinality } => {
+	let fields = s.fields.iter().enumerate().map(|(i, f)| match f {
+		Field::Node { name, ty, cardinality, optional } | Field::Token { name, ty, cardinality, optional } => {
+			let expect = format_ident!("expect_{}", name);
+			let message = format!("{} is missing its {}, which only happens in a tree with errors", s.name, name);
 			let name = format_ident!("{}", name);
 			let ty = format_ident!("{}", ty);
+			let get = match (cardinality, s.span(i)) {
+				(Cardinality::One(_), Some(span)) => span_child(s, &span, &ty),
+				(Cardinality::One(n), None) => quote! { children(&self.0).nth(#n) },
+				(Cardinality::Many, _) => quote!(),
+			};
 			match cardinality {
 				Cardinality::Many => quote! {
 					pub fn #name(&self) -> impl Iterator<Item = #ty> + '_ {
 						children(&self.0)
 					}
 				},
-				Cardinality::One(n) => quote! {
+				Cardinality::One(_) if *optional => quote! {
 					pub fn #name(&self) -> Option<#ty> {
-						children(&self.0).nth(#n)
+						#get
 					}
 				},
-			}
-		},
-		Field::Token { name, ty, cardinality } => {
-			let name = format_ident!("{}", name);
-			let ty = format_ident!("{}", ty);
-			match cardinality {
-				Cardinality::Many => {
-					quote! {
-						pub fn #name(&self) -> impl Iterator<Item = #ty> + '_ {
-							children(&self.0)
-						}
+				Cardinality::One(_) => quote! {
+					pub fn #name(&self) -> Option<#ty> {
+						#get
 					}
-				},
-				Cardinality::One(n) => {
-					quote! {
-						pub fn #name(&self) -> Option<#ty> {
-							children(&self.0).nth(#n)
-						}
+
+					/// Required by the grammar, so it's only missing from a tree with errors, where this panics
+					pub fn #expect(&self) -> #ty {
+						self.#name().expect(#message)
 					}
 				},
 			}
 		},
+		Field::Separated { name, ty, separator, optional } => {
+			let expect = format_ident!("expect_first_{}", name);
+			let message = format!("{} has no {}, which only happens in a tree with errors", s.name, name);
+			let name = format_ident!("{}", name);
+			let (ty, separator) = (format_ident!("{}", ty), format_ident!("{}", separator));
+			let list = quote! {
+				pub fn #name(&self) -> SeparatedList<#ty, #separator> {
+					SeparatedList::new(&self.0)
+				}
+			};
+			if *optional { return list }
+
+			quote! {
+				#list
+
+				/// The grammar requires an element in the list, so it's only empty in a tree with errors, where this panics
+				pub fn #expect(&self) -> #ty {
+					self.#name().first().expect(#message)
+				}
+			}
+		},
+		Field::Group { name, items } => generate_group(name, items),
 	});
 	
 	quote! {
@@ -84,14 +141,7 @@ fn generate_struct<'a, M: KindsMetaInfo>(_: &mut Generator<'a, M>, s: Struct) ->
 				let node = elem.into_node()?;
 				Self::can_cast(node.kind()).then(|| Self(node))
 			}
-			fn span(&self) -> FileSpan {
-				let range = self.0.text_range();
-				FileSpan {
-					start: range.start().into(),
-					end: range.end().into(),
-					relative: (),
-				}
-			}
+			fn span(&self) -> Span { blanket_impls::node_span(&self.0) }
 			fn inner(self) -> SyntaxElement { self.0.into() }
 		}
 		
@@ -102,7 +152,36 @@ fn generate_struct<'a, M: KindsMetaInfo>(_: &mut Generator<'a, M>, s: Struct) ->
 	}
 }
 
-fn generate_enum<'a, M: KindsMetaInfo>(generator: &mut Generator<'a, M>, e: Enum) -> proc_macro2::TokenStream {
+/// Iterator over the tuples of a repeated sequence, each one starts at an element of its first type
+/// that is right before elements of the other types, in order
+fn generate_group(name: &str, items: &[Field]) -> proc_macro2::TokenStream {
+	let name = format_ident!("{}", name);
+	let types: Vec<_> = items.iter().map(|item| format_ident!("{}", item.ty())).collect();
+	let vars: Vec<_> = (0..items.len()).map(|i| format_ident!("x{}", i)).collect();
+
+	let (first_ty, rest_types) = types.split_first().expect("a sequence has items");
+	let (first_var, rest_vars) = vars.split_first().expect("a sequence has items");
+
+	quote! {
+		pub fn #name(&self) -> impl Iterator<Item = (#(#types),*)> + '_ {
+			let mut elements = elements(&self.0)
+				.filter(|x| false #(|| #types::can_cast(x.kind()))*)
+				.peekable();
+
+			std::iter::from_fn(move || loop {
+				let Some(#first_var) = #first_ty::cast(elements.next()?) else { continue };
+				#(
+					let Some(#rest_vars) = elements.peek().cloned().and_then(#rest_types::cast) else { continue };
+					elements.next();
+				)*
+
+				return Some((#(#vars),*));
+			})
+		}
+	}
+}
+
+fn generate_enum(generator: &Generator, e: &Enum) -> proc_macro2::TokenStream {
 	let name = format_ident!("{}", e.name);
 	let token_variants: Vec<_> = e.token_variants.iter().map(|x| format_ident!("{}", x)).collect();
 
@@ -111,7 +190,7 @@ fn generate_enum<'a, M: KindsMetaInfo>(generator: &mut Generator<'a, M>, e: Enum
 	let mut struct_variants = Vec::new();
 	let mut enum_variants = Vec::new();
 
-	for x in e.node_variants {
+	for x in &e.node_variants {
 		match generator.node_types[&x.node] {
 			NodeType::Struct => struct_variants.push(format_ident!("{}", x.name)),
 			NodeType::Enum => enum_variants.push(format_ident!("{}", x.name)),
@@ -154,7 +233,7 @@ fn generate_enum<'a, M: KindsMetaInfo>(generator: &mut Generator<'a, M>, e: Enum
 				} #(.or_else(|| AstElement::cast(elem.clone()).map(Self::#enum_variants)))*
 			}
 
-			fn span(&self) -> FileSpan {
+			fn span(&self) -> Span {
 				match self {
 					#(Self::#token_variants(x) => x.span(),)*
 					#(Self::#node_variants(x) => x.span(),)*
@@ -0,0 +1,412 @@
+// Standard Uses
+use std::str::FromStr;
+
+// Crate Uses
+use crate::generator::error::GenError;
+
+// External Uses
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use ungrammar::Grammar;
+
+
+
+/// Options that change the shape of the generated code, without changing what grammar it's for
+#[derive(Debug, Clone, Default)]
+pub struct GeneratorOptions {
+	pub backend: Backend,
+	/// Type returned by `AstElement::span`, when not given it's `ungrammar_extra::TextRange`,
+	/// otherwise the conversion is a `fn(TextRange) -> Span`
+	pub span: Option<CustomType>,
+	/// Type returned by `AstToken::text`, when not given it's `&str`, otherwise
+	/// the conversion is a `fn(&SyntaxToken) -> Text`
+	pub text: Option<CustomType>,
+	/// Nodes that aren't generated since they're written by hand, (ex: rust-analyzer's `TokenTree`)
+	pub handwritten: Vec<HandwrittenNode>,
+	/// Code that goes along with generated nodes, (ex: `impl` blocks with extra methods)
+	pub node_hooks: Vec<NodeHook>,
+}
+
+/// A grammar node the user writes instead of the generator, the generated code still reads, builds
+/// and prints it, so it has to implement `AstElement` and `printer::ToSource`, plus `Debug`, `Clone`,
+/// `PartialEq`, `Eq` and `Hash` when it's a variant of a generated enum
+#[derive(Debug, Clone)]
+pub struct HandwrittenNode {
+	pub node: String,
+	/// Items that take the place of the node in `ast.rs`, when not given it's only left out
+	pub replacement: Option<String>,
+}
+
+/// Items put in `ast.rs` right after a generated node
+#[derive(Debug, Clone)]
+pub struct NodeHook {
+	pub node: String,
+	pub items: String,
+}
+
+/// A type of the generated API that is replaced by one of the user's
+#[derive(Debug, Clone)]
+pub struct CustomType {
+	/// Path of the type, as the generated code refers to it
+	pub path: String,
+	/// Path of the function that converts into the type
+	pub convert: String,
+}
+
+impl GeneratorOptions {
+	/// Body of `AstToken::text` for a token wrapper
+	pub(crate) fn token_text(&self) -> TokenStream {
+		let Some(text) = &self.text else { return self.backend.token_text() };
+		let convert: TokenStream = text.convert.parse().expect("options are validated before generating");
+
+		quote! { fn text(&self) -> Text { #convert(&self.0) } }
+	}
+
+	/// Aliases for the span and text types, along with the `AstToken` trait that depends on them
+	pub(crate) fn api_items(&self) -> String {
+		let span = match &self.span {
+			Some(span) => format!("pub type Span = {};\n", span.path),
+			None => "pub type Span = ungrammar_extra::TextRange;\n".to_owned(),
+		};
+
+		let text = match &self.text {
+			Some(text) => format!("pub type Text = {};\n\npub trait AstToken: Sized {{ fn text(&self) -> Text; }}\n", text.path),
+			None => self.backend.token_trait().to_owned(),
+		};
+
+		format!("{span}\n{text}")
+	}
+
+	/// Expression of a `Span` from the `range` of a node or token
+	pub(crate) fn span_of(&self, range: &str) -> String {
+		match &self.span {
+			Some(span) => format!("{}({range})", span.convert),
+			None => range.to_owned(),
+		}
+	}
+
+	/// If the node is written by hand instead of generated
+	pub(crate) fn is_handwritten(&self, node: &str) -> bool { self.handwritten.iter().any(|h| h.node == node) }
+
+	/// Items of every hook of the node, in the order they were given
+	pub(crate) fn hooks_of(&self, node: &str) -> TokenStream {
+		self.node_hooks
+			.iter()
+			.filter(|hook| hook.node == node)
+			.map(|hook| hook.items.parse::<TokenStream>().expect("options are validated before generating"))
+			.collect()
+	}
+
+	/// Items that replace the hand-written nodes
+	pub(crate) fn replacements(&self) -> TokenStream {
+		self.handwritten
+			.iter()
+			.filter_map(|h| h.replacement.as_ref())
+			.map(|items| items.parse::<TokenStream>().expect("options are validated before generating"))
+			.collect()
+	}
+
+	/// Checks that the given types, conversions and items are valid Rust, so they can be spliced
+	/// into the generated code, and that the nodes they're for are in the grammar
+	pub(crate) fn validate(&self, grammar: &Grammar) -> Result<(), GenError> {
+		let custom = [("span", &self.span), ("text", &self.text)];
+
+		for (option, custom) in custom.into_iter().filter_map(|(o, c)| Some((o, c.as_ref()?))) {
+			let invalid = |value: &str| GenError::InvalidOption { option, value: value.to_owned() };
+
+			syn::parse_str::<syn::Type>(&custom.path).map_err(|_| invalid(&custom.path))?;
+			syn::parse_str::<syn::Path>(&custom.convert).map_err(|_| invalid(&custom.convert))?;
+		}
+
+		let is_node = |name: &str| grammar.iter().any(|node| grammar[node].name == name);
+		let nodes = self.handwritten.iter().map(|h| ("handwritten", &h.node, h.replacement.as_ref()));
+		let hooks = self.node_hooks.iter().map(|hook| ("node hook", &hook.node, Some(&hook.items)));
+
+		for (option, node, items) in nodes.chain(hooks) {
+			let invalid = |value: &str| GenError::InvalidOption { option, value: value.to_owned() };
+
+			// A hook on a hand-written node would have nothing to go along with
+			if !is_node(node) || (option == "node hook" && self.is_handwritten(node)) {
+				return Err(invalid(node));
+			}
+			if let Some(items) = items {
+				syn::parse_str::<syn::File>(items).map_err(|_| invalid(items))?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// Syntax tree library the generated code is built on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+	#[default]
+	Cstree,
+	Rowan,
+}
+
+impl Backend {
+	/// Attributes for the `SyntaxKind` enum
+	pub(crate) fn kind_attributes(&self) -> TokenStream {
+		match self {
+			Self::Cstree => quote! {
+				#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, cstree::Syntax)]
+				#[repr(u32)]
+			},
+			Self::Rowan => quote! {
+				#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+				#[repr(u16)]
+			},
+		}
+	}
+
+	/// Extra items that go along with the `SyntaxKind` enum, rowan needs a language type
+	/// to tie the kinds to its untyped tree
+	pub(crate) fn kind_items(&self) -> Option<TokenStream> {
+		match self {
+			Self::Cstree => None,
+			Self::Rowan => Some(quote! {
+				#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+				pub enum Lang {}
+
+				impl rowan::Language for Lang {
+					type Kind = SyntaxKind;
+
+					fn kind_from_raw(raw: rowan::SyntaxKind) -> Self::Kind {
+						assert!(raw.0 <= SyntaxKind::Eof as u16);
+						unsafe { std::mem::transmute::<u16, SyntaxKind>(raw.0) }
+					}
+
+					fn kind_to_raw(kind: Self::Kind) -> rowan::SyntaxKind { rowan::SyntaxKind(kind as u16) }
+				}
+
+				impl From<SyntaxKind> for rowan::SyntaxKind {
+					fn from(kind: SyntaxKind) -> Self { Self(kind as u16) }
+				}
+			}),
+		}
+	}
+
+	/// `AstToken` trait when the text is the backend's own, cstree only has the text of
+	/// trees built with a resolver (ex: `SyntaxNode::new_root_with_resolver`)
+	fn token_trait(&self) -> &'static str {
+		match self {
+			Self::Cstree => indoc::indoc! {"
+                /// The text is only there when the tree was built with a resolver, its interner
+                /// can be given to `SyntaxToken::resolve_text` otherwise, with rowan it's a plain `&str`
+                pub trait AstToken: Sized { fn text(&self) -> Option<&str>; }
+            "},
+			Self::Rowan => indoc::indoc! {"
+                /// rowan trees always hold their text, so unlike with the default backend it isn't
+                /// an `Option` of the text
+                pub trait AstToken: Sized { fn text(&self) -> &str; }
+            "},
+		}
+	}
+
+	/// Body of `AstToken::text` for a token wrapper, when the text is the backend's own
+	fn token_text(&self) -> TokenStream {
+		match self {
+			Self::Cstree => quote! {
+				fn text(&self) -> Option<&str> { self.0.try_resolved().map(|token| token.text()) }
+			},
+			Self::Rowan => quote! {
+				fn text(&self) -> &str { self.0.text() }
+			},
+		}
+	}
+
+	/// Text of an untyped token, the printer works on it since `AstToken::text` may be a custom type
+	pub(crate) fn raw_token_text(&self) -> TokenStream {
+		match self {
+			Self::Cstree => quote! {
+				fn text(token: &SyntaxToken) -> Option<&str> { token.try_resolved().map(|token| token.text()) }
+			},
+			Self::Rowan => quote! {
+				fn text(token: &SyntaxToken) -> Option<&str> { Some(token.text()) }
+			},
+		}
+	}
+
+	/// Return type and body of the `make` function of a node, on cstree it fails when a child has no text
+	pub(crate) fn make_node(&self, ty: &Ident) -> (TokenStream, TokenStream) {
+		match self {
+			Self::Cstree => (
+				quote! { Result<#ty, MakeError> },
+				quote! { build::node(SyntaxKind::#ty, children).map(|node| #ty::cast(node.into()).unwrap()) },
+			),
+			Self::Rowan => (quote! { #ty }, quote! { #ty::cast(build::node(SyntaxKind::#ty, children).into()).unwrap() }),
+		}
+	}
+
+	/// Crate uses of the module that holds the syntax tree aliases and AST traits
+	pub(crate) fn traits_uses(&self) -> Vec<String> {
+		match self {
+			Self::Cstree => vec!["kind::SyntaxKind".to_string()],
+			Self::Rowan => vec!["kind::{Lang, SyntaxKind}".to_string()],
+		}
+	}
+
+	/// Aliases of the untyped syntax tree and the `elements` and `children` helpers, since they depend on the backend
+	pub(crate) fn syntax_items(&self) -> &'static str {
+		match self {
+			Self::Cstree => indoc::indoc! {r#"
+                pub type SyntaxNode = cstree::syntax::SyntaxNode<SyntaxKind>;
+                pub type SyntaxToken = cstree::syntax::SyntaxToken<SyntaxKind>;
+                pub type SyntaxElement = cstree::syntax::SyntaxElement<SyntaxKind>;
+                pub type SyntaxElementRef<'a> = cstree::syntax::SyntaxElementRef<'a, SyntaxKind>;
+                pub type SyntaxNodeChildren<'a> = cstree::syntax::SyntaxNodeChildren<'a, SyntaxKind>;
+
+                pub type ResolvedNode = cstree::syntax::ResolvedNode<SyntaxKind>;
+                pub type ResolvedToken = cstree::syntax::ResolvedToken<SyntaxKind>;
+                pub type ResolvedElement = cstree::syntax::ResolvedElement<SyntaxKind>;
+
+                fn elements(node: &SyntaxNode) -> impl Iterator<Item = SyntaxElement> + '_ {
+                    node.children_with_tokens().map(|x| match x {
+                        SyntaxElementRef::Node(node) => SyntaxElement::Node(node.clone()),
+                        SyntaxElementRef::Token(token) => SyntaxElement::Token(token.clone()),
+                    })
+                }
+
+                fn children<'a, T: 'a + AstElement>(node: &'a SyntaxNode) -> impl Iterator<Item = T> + 'a {
+                    elements(node).filter_map(T::cast)
+                }
+            "#},
+			Self::Rowan => indoc::indoc! {r#"
+                pub type SyntaxNode = rowan::SyntaxNode<Lang>;
+                pub type SyntaxToken = rowan::SyntaxToken<Lang>;
+                pub type SyntaxElement = rowan::SyntaxElement<Lang>;
+                pub type SyntaxNodeChildren = rowan::SyntaxNodeChildren<Lang>;
+                pub type SyntaxElementChildren = rowan::SyntaxElementChildren<Lang>;
+
+                fn elements(node: &SyntaxNode) -> impl Iterator<Item = SyntaxElement> + '_ {
+                    node.children_with_tokens()
+                }
+
+                fn children<'a, T: 'a + AstElement>(node: &'a SyntaxNode) -> impl Iterator<Item = T> + 'a {
+                    elements(node).filter_map(T::cast)
+                }
+            "#},
+		}
+	}
+
+	/// Helpers `make` builds its nodes and tokens with, they're in a module of their own so they
+	/// can't clash with the functions of the grammar's nodes (ex: `make::node` of a `Node`)
+	pub(crate) fn make_items(&self) -> &'static str {
+		match self {
+			// The green nodes of cstree hold keys of their tree's interner, so the children are
+			// rebuilt into the new tree with its own interner, which needs their text
+			Self::Cstree => indoc::indoc! {r#"
+                /// A child given to a `make` function comes from a tree built without a resolver,
+                /// so its tokens have no text to build the new tree with
+                #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+                pub struct MakeError {
+                    /// Kind of the first token that has no text
+                    pub kind: SyntaxKind,
+                }
+
+                impl std::fmt::Display for MakeError {
+                    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(f, "{:?} token has no text, its tree was built without a resolver", self.kind)
+                    }
+                }
+
+                impl std::error::Error for MakeError {}
+
+                mod build {
+                    use super::*;
+
+                    type Builder = cstree::build::GreenNodeBuilder<'static, 'static, SyntaxKind>;
+
+                    fn push(builder: &mut Builder, element: SyntaxElementRef<'_>) -> Result<(), MakeError> {
+                        match element {
+                            SyntaxElementRef::Node(node) => {
+                                builder.start_node(node.kind());
+                                for child in node.children_with_tokens() {
+                                    push(builder, child)?;
+                                }
+                                builder.finish_node();
+                            },
+                            SyntaxElementRef::Token(token) => {
+                                let resolved = token.try_resolved().ok_or(MakeError { kind: token.kind() })?;
+                                builder.token(token.kind(), resolved.text());
+                            },
+                        }
+                        Ok(())
+                    }
+
+                    fn tree(build: impl FnOnce(&mut Builder) -> Result<(), MakeError>) -> Result<SyntaxNode, MakeError> {
+                        let mut builder = Builder::new();
+                        build(&mut builder)?;
+
+                        let (green, cache) = builder.finish();
+                        let interner = cache.and_then(|cache| cache.into_interner()).expect("the builder owns its interner");
+                        Ok(SyntaxNode::new_root_with_resolver(green, interner).syntax().clone())
+                    }
+
+                    pub(super) fn node(kind: SyntaxKind, children: Vec<SyntaxElement>) -> Result<SyntaxNode, MakeError> {
+                        tree(|builder| {
+                            builder.start_node(kind);
+                            for child in &children {
+                                match child {
+                                    SyntaxElement::Node(node) => push(builder, SyntaxElementRef::Node(node))?,
+                                    SyntaxElement::Token(token) => push(builder, SyntaxElementRef::Token(token))?,
+                                }
+                            }
+                            builder.finish_node();
+                            Ok(())
+                        })
+                    }
+
+                    pub(super) fn token(kind: SyntaxKind, text: &str) -> SyntaxToken {
+                        let root = tree(|builder| {
+                            builder.start_node(SyntaxKind::Eof);
+                            builder.token(kind, text);
+                            builder.finish_node();
+                            Ok(())
+                        });
+                        root.expect("the text is given").first_token().expect("the token was just added").clone()
+                    }
+                }
+            "#},
+			// Green nodes of rowan don't depend on their tree, so the children are put in as they are
+			Self::Rowan => indoc::indoc! {r#"
+                mod build {
+                    use super::*;
+
+                    use rowan::{GreenNode, GreenToken, NodeOrToken};
+
+                    fn green(element: SyntaxElement) -> NodeOrToken<GreenNode, GreenToken> {
+                        match element {
+                            SyntaxElement::Node(node) => NodeOrToken::Node(node.green().into_owned()),
+                            SyntaxElement::Token(token) => NodeOrToken::Token(token.green().to_owned()),
+                        }
+                    }
+
+                    pub(super) fn node(kind: SyntaxKind, children: Vec<SyntaxElement>) -> SyntaxNode {
+                        SyntaxNode::new_root(GreenNode::new(kind.into(), children.into_iter().map(green)))
+                    }
+
+                    pub(super) fn token(kind: SyntaxKind, text: &str) -> SyntaxToken {
+                        let token = NodeOrToken::Token(GreenToken::new(kind.into(), text));
+                        let root = SyntaxNode::new_root(GreenNode::new(SyntaxKind::Eof.into(), [token]));
+                        root.first_token().expect("the token was just added")
+                    }
+                }
+            "#},
+		}
+	}
+}
+
+impl FromStr for Backend {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"cstree" => Ok(Self::Cstree),
+			"rowan" => Ok(Self::Rowan),
+			_ => Err(format!("unknown backend '{s}', expected 'cstree' or 'rowan'")),
+		}
+	}
+}
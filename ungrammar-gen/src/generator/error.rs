@@ -0,0 +1,155 @@
+// Standard Uses
+use std::{fmt, path::PathBuf};
+
+
+
+/// Everything that can go wrong while generating code from a grammar
+#[derive(Debug)]
+pub enum GenError {
+	/// The grammar file couldn't be read
+	Read { path: PathBuf, source: std::io::Error },
+	/// The grammar file is not valid ungrammar
+	Parse(ungrammar::Error),
+	/// The grammar is valid ungrammar, but some of its rules can't be lowered into
+	/// AST code, every problem found in a run is collected here
+	Lowering(Vec<Diagnostic>),
+	/// A generated file couldn't be written
+	Write { path: PathBuf, source: std::io::Error },
+	/// Formatting a generated file with rustfmt failed
+	Format { path: PathBuf, reason: String },
+	/// A generator option is not valid, (ex: a custom type that is not a Rust type)
+	InvalidOption { option: &'static str, value: String },
+	/// The generated files on disk are missing or differ from what the grammar generates
+	Stale(Vec<PathBuf>),
+	/// `OUT_DIR` is not set, which means the code is not being generated from a build script
+	MissingOutDir,
+}
+
+/// A problem with a single rule of the grammar
+#[derive(Debug)]
+pub struct Diagnostic {
+	/// Name of the grammar node the rule belongs to
+	pub node: String,
+	/// The failing rule, formatted back to its grammar form
+	pub rule: String,
+	pub kind: DiagnosticKind,
+}
+
+#[derive(Debug)]
+pub enum DiagnosticKind {
+	/// The type was already used by the node in a way that conflicts with this use,
+	/// (ex: a type that appears both on its own and in a repetition)
+	DuplicateType { ty: String },
+	/// The rule can't be used where it is (ex: an alternation inside a sequence)
+	UnexpectedRule,
+	/// The node is lowered into an enum, but its rule is not an alternation
+	ExpectedAlternation,
+	/// An alternation inside a sequence has no label to name its enum after
+	UnlabeledAlternation,
+	/// The repeated rule can't be iterated over (ex: a repeated optional, or a sequence with more than nodes and tokens)
+	UnsupportedRepetition,
+	/// Several optional fields of the type have no required token in between them, so which
+	/// of them is in the tree can't be told (ex: `Literal? Literal?`)
+	AmbiguousOptional { ty: String },
+	/// There is no token kind information for the literal
+	UnknownToken {
+		literal: String,
+		/// Where the token is used in the grammar, if the grammar source is known
+		position: Option<Position>,
+		/// Known literals that are close to this one
+		suggestions: Vec<String>,
+	},
+}
+
+/// A position in the grammar source, both line and column start at 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+	pub line: usize,
+	pub column: usize,
+}
+
+impl fmt::Display for GenError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Read { path, .. } => write!(f, "Couldn't read file at path '{}'", path.display()),
+			Self::Parse(error) => write!(f, "Couldn't parse grammar: {error}"),
+			Self::Lowering(diagnostics) => {
+				write!(f, "Found {} problem(s) in the grammar:", diagnostics.len())?;
+				for diagnostic in diagnostics {
+					write!(f, "\n  - {diagnostic}")?;
+				}
+				Ok(())
+			},
+			Self::Write { path, .. } => write!(f, "Could not write into file at: {}", path.display()),
+			Self::Format { path, reason } => write!(f, "Could not format file at {}: {reason}", path.display()),
+			Self::InvalidOption { option, value } => write!(f, "Invalid {option} option: `{value}`"),
+			Self::Stale(files) => {
+				write!(f, "Generated code is out of date with the grammar, regenerate it:")?;
+				for file in files {
+					write!(f, "\n  - {}", file.display())?;
+				}
+				Ok(())
+			},
+			Self::MissingOutDir => write!(f, "OUT_DIR is not set, it's only set while running build scripts"),
+		}
+	}
+}
+
+impl std::error::Error for GenError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Read { source, .. } | Self::Write { source, .. } => Some(source),
+			Self::Parse(error) => Some(error),
+			Self::Lowering(_) | Self::Format { .. } | Self::InvalidOption { .. } | Self::Stale(_) | Self::MissingOutDir => None,
+		}
+	}
+}
+
+impl fmt::Display for Diagnostic {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "node `{}`, rule `{}`: {}", self.node, self.rule, self.kind)
+	}
+}
+
+impl fmt::Display for DiagnosticKind {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::DuplicateType { ty } => write!(f, "uses type `{ty}` which was already used before"),
+			Self::UnexpectedRule => write!(f, "rule is not allowed in this position"),
+			Self::ExpectedAlternation => write!(f, "expected an alternation rule"),
+			Self::UnlabeledAlternation => write!(f, "alternation needs a label to name its enum (ex: `op:('+' | '-')`)"),
+			Self::UnsupportedRepetition => write!(
+				f, "only nodes, tokens, alternations and sequences of nodes and tokens can be repeated"
+			),
+			Self::AmbiguousOptional { ty } => write!(
+				f, "has optional fields of type `{ty}` that can't be told apart, put a required token in between them"
+			),
+			Self::UnknownToken { literal, position, suggestions } => {
+				write!(f, "no token kind information found for literal '{literal}'")?;
+				if let Some(position) = position {
+					write!(f, " at {position}")?;
+				}
+
+				if let Some((last, rest)) = suggestions.split_last() {
+					write!(f, ", did you mean ")?;
+					for (i, suggestion) in rest.iter().enumerate() {
+						if i > 0 {
+							write!(f, ", ")?;
+						}
+						write!(f, "'{suggestion}'")?;
+					}
+					if !rest.is_empty() {
+						write!(f, " or ")?;
+					}
+					write!(f, "'{last}'?")?;
+				}
+
+				Ok(())
+			},
+		}
+	}
+}
+
+impl fmt::Display for Position {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "line {}, column {}", self.line, self.column) }
+}
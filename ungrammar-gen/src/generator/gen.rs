@@ -1,16 +1,23 @@
 // Standard Uses
-use std::{collections::HashMap, marker::PhantomData, path::Path};
+use std::{collections::{BTreeMap, HashMap}, path::Path};
 
 // Crate Uses
-use super::utils::{
-	format::{format_rule, pluralize},
-	io::{self, Uses}, lint::to_snake_case,
+use super::{
+	backend::GeneratorOptions,
+	error::{Diagnostic, DiagnosticKind, GenError},
+	meta::KindsMeta,
+	utils::{
+		format::{format_rule, pluralize},
+		io::{self, GeneratedFile, Uses}, lint::{to_pascal_case, to_snake_case},
+		source::GrammarSource, suggest::similar,
+	},
 };
 
 // External Uses
-use quote::{format_ident, quote};
-use ungrammar::{Grammar, Node, Rule};
-use ungrammar_extra::KindsMetaInfo;
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use ungrammar::{Grammar, Node, Rule, Token};
+use ungrammar_extra::{Fixity, SyntaxKindMeta, TokenCategory};
 
 
 
@@ -26,38 +33,94 @@ pub enum NodeData {
 
 pub struct Struct {
 	pub name: String,
+	pub node: Node,
 	pub fields: Vec<Field>,
 	pub type_cardinality: HashMap<String, Cardinality>,
 }
 
 impl Struct {
-	fn get_cardinality(&mut self, ty: &String, rule: &Rule, grammar: &Grammar) -> usize {
+	fn get_cardinality(&mut self, ty: &String) -> Result<usize, DiagnosticKind> {
 		match self.type_cardinality.get_mut(ty) {
 			Some(Cardinality::One(x)) => {
 				*x += 1;
-				*x
+				Ok(*x)
 			},
-			Some(Cardinality::Many) => panic!(
-				"rule `{}` uses type `{}` which was already used before",
-				format_rule(rule, grammar),
-				ty
-			),
+			Some(Cardinality::Many) => Err(DiagnosticKind::DuplicateType { ty: ty.clone() }),
 			None => {
 				self.type_cardinality.insert(ty.clone(), Cardinality::One(0));
-				0
+				Ok(0)
 			},
 		}
 	}
 
-	fn use_many_cardinality(&mut self, ty: &str, r: &Rule, grammar: &Grammar) {
+	/// Every element of the type is taken by the field, so it can't have been used before or after
+	fn use_many_cardinality(&mut self, ty: &str) -> Result<(), DiagnosticKind> {
 		if self.type_cardinality.contains_key(ty) {
-			panic!(
-				"rule `{}` uses type `{}` which was already used before",
-				format_rule(r, grammar),
-				ty
-			);
+			return Err(DiagnosticKind::DuplicateType { ty: ty.to_owned() });
 		}
+
+		self.type_cardinality.insert(ty.to_owned(), Cardinality::Many);
+		Ok(())
+	}
+
+	/// Single fields of the type, by their index in `fields`
+	fn fields_of<'a>(&'a self, ty: &'a str) -> impl Iterator<Item = usize> + 'a {
+		self.fields.iter().enumerate().filter_map(move |(i, field)| match field {
+			Field::Node { ty: t, cardinality: Cardinality::One(_), .. } | Field::Token { ty: t, cardinality: Cardinality::One(_), .. }
+				if t == ty => Some(i),
+			_ => None,
+		})
+	}
+
+	/// When one of several fields of the type is optional, their position among the children
+	/// of the type doesn't tell which field a child is
+	fn is_ambiguous(&self, ty: &str) -> bool {
+		self.fields_of(ty).count() > 1 && self.fields_of(ty).any(|i| self.fields[i].is_optional())
 	}
+
+	/// Where the single field at `index` is in the tree when its type is ambiguous, it's found in
+	/// between the required tokens around it, `None` when its position among its type is enough
+	pub fn span(&self, index: usize) -> Option<Span> {
+		let ty = match &self.fields[index] {
+			Field::Node { ty, cardinality: Cardinality::One(_), .. } | Field::Token { ty, cardinality: Cardinality::One(_), .. } => ty,
+			_ => return None,
+		};
+		if !self.is_ambiguous(ty) { return None }
+
+		// A required token of an unambiguous type is always found, so the children around it are known
+		let is_anchor = |i: &usize| match &self.fields[*i] {
+			Field::Token { ty, cardinality: Cardinality::One(_), optional: false, .. } => !self.is_ambiguous(ty),
+			_ => false,
+		};
+		let after = (0..index).rev().find(is_anchor);
+		let before = (index + 1..self.fields.len()).find(is_anchor);
+
+		let range = after.map_or(0, |i| i + 1)..before.unwrap_or(self.fields.len());
+		let fields: Vec<_> = self.fields_of(ty).filter(|i| range.contains(i)).collect();
+		let optional: Vec<_> = (0..fields.len()).filter(|p| self.fields[fields[*p]].is_optional()).collect();
+
+		Some(Span {
+			after,
+			before,
+			position: fields.iter().position(|i| *i == index).unwrap(),
+			required: fields.len() - optional.len(),
+			optional,
+		})
+	}
+}
+
+/// The children in between two required tokens of a struct, which hold a field of an ambiguous type
+pub struct Span {
+	/// Index of the field of the token the span starts after, when it doesn't start with the node
+	pub after: Option<usize>,
+	/// Index of the field of the token the span ends before, when it doesn't end with the node
+	pub before: Option<usize>,
+	/// Position of the field among the fields of its type in the span
+	pub position: usize,
+	/// Positions of the optional fields of the type in the span, only one of them can be told apart
+	pub optional: Vec<usize>,
+	/// Number of fields of the type in the span that are always there
+	pub required: usize,
 }
 
 pub enum Field {
@@ -65,14 +128,55 @@ pub enum Field {
 		name: String,
 		ty: String,
 		cardinality: Cardinality,
+		/// If the grammar lets it be missing (ex: `'pub'?` or `Attr*`), a field that isn't optional
+		/// is only missing from a tree with errors
+		optional: bool,
 	},
 	Node {
 		name: String,
 		ty: String,
 		cardinality: Cardinality,
+		optional: bool,
+	},
+	/// Nodes separated by a token (ex: `Param (',' Param)* ','?`), which keeps the separators around
+	Separated {
+		name: String,
+		ty: String,
+		separator: String,
+		optional: bool,
+	},
+	/// A repeated sequence (ex: `(',' Param)*`), iterated over as tuples of its items,
+	/// which are single nodes and tokens
+	Group {
+		name: String,
+		items: Vec<Field>,
 	},
 }
 
+impl Field {
+	pub fn name(&self) -> &str {
+		match self {
+			Self::Token { name, .. } | Self::Node { name, .. } | Self::Separated { name, .. } | Self::Group { name, .. } => name,
+		}
+	}
+
+	/// If the grammar lets the field be missing, a group is as it's repeated
+	pub fn is_optional(&self) -> bool {
+		match self {
+			Self::Token { optional, .. } | Self::Node { optional, .. } | Self::Separated { optional, .. } => *optional,
+			Self::Group { .. } => true,
+		}
+	}
+
+	/// Type of a node or token field, a group has no single type
+	pub fn ty(&self) -> &str {
+		match self {
+			Self::Token { ty, .. } | Self::Node { ty, .. } | Self::Separated { ty, .. } => ty,
+			Self::Group { .. } => panic!("a group field has no single type"),
+		}
+	}
+}
+
 pub enum Cardinality {
 	One(usize),
 	Many,
@@ -89,75 +193,226 @@ pub struct Enum {
 	pub token_variants: Vec<String>,
 }
 
-pub struct Generator<'a, M: KindsMetaInfo> {
-	grammar: &'a Grammar,
+pub struct Generator<'a> {
+	pub(crate) grammar: &'a Grammar,
 	pub node_types: HashMap<Node, NodeType>,
 
 	token_kind_namespace: String,
-	meta: PhantomData<M>,
+	meta: &'a KindsMeta,
+	source: Option<GrammarSource>,
+	pub(crate) options: GeneratorOptions,
+	diagnostics: Vec<Diagnostic>,
+	/// Enums made for the alternations inside the struct being lowered
+	inline_enums: Vec<Enum>,
 }
 
-impl<'a, M: KindsMetaInfo + 'static> Generator<'a, M> {
-	pub fn new(grammar: &'a Grammar, token_kind_namespace: String) -> Self {
+impl<'a> Generator<'a> {
+	pub fn new(grammar: &'a Grammar, meta: &'a KindsMeta, token_kind_namespace: String) -> Self {
 		Self {
 			grammar,
 			node_types: HashMap::new(),
-			meta: Default::default(),
-			token_kind_namespace
+			meta,
+			token_kind_namespace,
+			source: None,
+			options: GeneratorOptions::default(),
+			diagnostics: Vec::new(),
+			inline_enums: Vec::new(),
+		}
+	}
+
+	pub fn with_options(mut self, options: GeneratorOptions) -> Self {
+		self.options = options;
+		self
+	}
+
+	/// Source text of the grammar, with it diagnostics can tell where in the grammar the problem is
+	pub fn with_source(mut self, source: &'a str) -> Self {
+		self.source = Some(GrammarSource::new(source));
+		self
+	}
+
+	/// Generates all the code and writes it into `out`, nothing is written if any rule
+	/// of the grammar can't be lowered, in that case every problem found is returned
+	pub fn generate(self, out: &Path) -> Result<(), GenError> {
+		for (name, text) in self.render(out)? {
+			io::write_generated(out, name, &text)?;
 		}
+
+		Ok(())
 	}
 
-	pub fn generate(mut self, out: &Path) {
+	/// Generates all the code without touching the filesystem, mapping each file name to its
+	/// formatted source, rustfmt configuration is looked up from `out` as if writing there
+	pub fn generate_to_memory(self, out: &Path) -> Result<BTreeMap<String, String>, GenError> {
+		let files = self.render(out)?;
+
+		Ok(files.into_iter().map(|(name, text)| (name.to_owned(), text)).collect())
+	}
+
+	/// Compares what would be generated with the files in `out`, without writing anything
+	pub fn check(self, out: &Path) -> Result<(), GenError> {
+		let mut stale = vec![];
+
+		for (name, text) in self.render(out)? {
+			let path = out.join(name);
+			let existing = match path.exists() {
+				true => Some(
+					std::fs::read_to_string(&path).map_err(|source| GenError::Read { path: path.clone(), source })?
+				),
+				false => None,
+			};
+
+			if existing.as_deref() != Some(&*text) {
+				stale.push(path);
+			}
+		}
+
+		match stale.is_empty() {
+			true => Ok(()),
+			false => Err(GenError::Stale(stale)),
+		}
+	}
+
+	/// Generates all the code as a single formatted file, with every module inlined so it can be
+	/// `include!`d, `path` is where the file would be written
+	pub fn generate_bundle(self, path: &Path) -> Result<String, GenError> {
+		let (root, files) = self.lower_bundle()?;
+
+		io::render_bundle(path, root, files)
+	}
+
+	/// Same as [`Generator::generate_bundle`] but unformatted, for when the code is only
+	/// consumed as tokens (ex: by a macro)
+	pub fn generate_source(self) -> Result<String, GenError> {
+		let (root, files) = self.lower_bundle()?;
+
+		Ok(io::bundle(root, files))
+	}
+
+	fn lower_bundle(self) -> Result<(GeneratedFile, Vec<GeneratedFile>), GenError> {
+		let mut files = self.lower()?;
+		let root = files.remove(files.iter().position(|f| f.name == "mod.rs").expect("mod.rs is always generated"));
+
+		Ok((root, files))
+	}
+
+	/// Generates every file in memory, formatted and ready to be written into `out`
+	fn render(self, out: &Path) -> Result<Vec<(&'static str, String)>, GenError> {
+		self.lower()?
+			.into_iter()
+			.map(|file| Ok((file.name, io::render_generated(out, file)?)))
+			.collect()
+	}
+
+	/// Lowers the grammar into the unformatted generated files
+	fn lower(mut self) -> Result<Vec<GeneratedFile>, GenError> {
+		self.options.validate(self.grammar)?;
 		self.init_node_types();
 
-		let token_kind_name = self.token_kind_namespace.parse().unwrap();
 		// let token_kind_name: proc_macro2::TokenStream = self.token_kind_namespace.parse().unwrap();
 		//let token_kind_name: syn::Type = syn::parse_str(&*self.token_kind_namespace).unwrap();
-		
-		io::write_generated(
-			out, "kind.rs",
-			Some(Uses {
-				mods: vec![],
-				std: vec![],
-				krate: vec![token_kind_name],
-				external: vec![],
-			}),
-			self.gen_kinds()
-		);
-		io::write_generated(
-			out, "token.rs",
-			Some(Uses {
-				mods: vec![],
-				std: vec![],
-				//krate: vec!["super::{*, nodes::*}".to_owned()],
-				krate: vec!["super::*".to_owned()],
-				external: vec!["diagnostics::FileSpan".to_owned()]
-			}),
-			self.gen_tokens(),
-		);
-		io::write_generated(
-			out, "ast.rs",
-			Some(Uses {
-				mods: vec![],
-				std: vec![],
-				//krate: vec!["super::{*, token::*, nodes::*, blanket_impls}".to_owned()],
-				krate: vec!["super::{*, token::*}".to_owned()],
-				external: vec!["diagnostics::FileSpan".to_owned()] 
-			}),
-			super::ast::generate_ast(&mut self),
-		);
-
-		self.gen_blanket_impls(out);
-		self.gen_ast_traits(out);
+
+		let nodes = self.gen_nodes();
+
+		let files = [
+			GeneratedFile {
+				name: "kind.rs",
+				uses: Some(Uses {
+					mods: vec![],
+					std: vec![],
+					krate: vec![self.token_kind_namespace.clone()],
+					external: vec![],
+				}),
+				content: self.gen_kinds(),
+			},
+			GeneratedFile {
+				name: "token.rs",
+				uses: Some(Uses {
+					mods: vec![],
+					std: vec![],
+					//krate: vec!["super::{*, nodes::*}".to_owned()],
+					krate: vec!["super::*".to_owned()],
+					external: vec![]
+				}),
+				content: self.gen_tokens(),
+			},
+			GeneratedFile {
+				name: "ast.rs",
+				uses: Some(Uses {
+					mods: vec![],
+					std: vec![],
+					//krate: vec!["super::{*, token::*, nodes::*, blanket_impls}".to_owned()],
+					krate: vec!["super::{*, token::*}".to_owned()],
+					external: vec![]
+				}),
+				content: super::ast::generate_ast(&self, &nodes),
+			},
+			self.gen_visitor(&nodes),
+			self.gen_make(&nodes),
+			self.gen_printer(&nodes),
+			self.gen_blanket_impls(),
+			self.gen_ast_traits(),
+		];
+
+		if !self.diagnostics.is_empty() {
+			return Err(GenError::Lowering(self.diagnostics));
+		}
+
+		Ok(files.into())
+	}
+
+	/// Kinds of the grammar tokens, in the order the grammar uses them
+	pub(crate) fn token_kinds(&self) -> impl Iterator<Item = &SyntaxKindMeta> {
+		self.grammar.tokens().filter_map(|n| self.meta.get(&self.grammar[n].name))
 	}
 
 	fn gen_kinds(&mut self) -> String {
-		let token_kinds: Vec<_> = self
-			.grammar
-			.tokens()
-			.map(|n| map_token::<M>(&self.grammar[n].name))
-			//.chain(["Whitespace", "Comment", "Error"].map(|s| (s.to_ascii_lowercase(), s.to_string())))
-			.collect();
+		// Trivia and error kinds are never in the grammar, but the tree still needs to hold them
+		let grammar_kinds: Vec<_> = self.token_kinds().collect();
+		let extra_kinds = self
+			.meta
+			.iter()
+			.filter(|kind| (kind.trivia || kind.error) && !grammar_kinds.iter().any(|k| k.name == kind.name));
+		let all_kinds: Vec<_> = grammar_kinds.iter().copied().chain(extra_kinds).collect();
+
+		let matching = |kind: &dyn Fn(&SyntaxKindMeta) -> bool| {
+			let kinds: Vec<_> = all_kinds.iter().filter(|k| kind(k)).map(|k| format_ident!("{}", k.name)).collect();
+			match kinds.is_empty() {
+				true => quote! { false },
+				false => quote! { matches!(self, #(Self::#kinds)|*) },
+			}
+		};
+		let is_trivia = matching(&|k| k.trivia);
+		let is_error = matching(&|k| k.error);
+		let is_keyword = matching(&|k| k.category == Some(TokenCategory::Keyword));
+		let is_punct = matching(&|k| k.category == Some(TokenCategory::Punct));
+		let is_literal = matching(&|k| k.category == Some(TokenCategory::Literal));
+		let is_ident = matching(&|k| k.category == Some(TokenCategory::Ident));
+
+		let binding_powers = |fixity: fn(Fixity) -> bool, power: fn((Option<u8>, Option<u8>)) -> TokenStream| {
+			let arms: Vec<_> = all_kinds
+				.iter()
+				.filter_map(|kind| {
+					let operator = kind.operators.iter().find(|op| fixity(op.fixity))?;
+					let (name, power) = (format_ident!("{}", kind.name), power(operator.binding_power()));
+					Some(quote! { Self::#name => Some(#power), })
+				})
+				.collect();
+
+			match arms.is_empty() {
+				true => quote! { None },
+				false => quote! { match self { #(#arms)* _ => None } },
+			}
+		};
+		let infix = binding_powers(|f| matches!(f, Fixity::Infix(_)), |(l, r)| {
+			let (l, r) = (Literal::u8_unsuffixed(l.unwrap()), Literal::u8_unsuffixed(r.unwrap()));
+			quote! { (#l, #r) }
+		});
+		let prefix = binding_powers(|f| f == Fixity::Prefix, |(_, r)| Literal::u8_unsuffixed(r.unwrap()).into_token_stream());
+		let postfix = binding_powers(|f| f == Fixity::Postfix, |(l, _)| Literal::u8_unsuffixed(l.unwrap()).into_token_stream());
+
+		let token_kinds: Vec<_> = all_kinds.iter().map(|kind| (kind.literal.clone(), kind.name.clone())).collect();
+		let token_descriptions: Vec<_> = all_kinds.iter().map(|kind| &kind.description).collect();
 
 		let node_kinds: Vec<_> = self
 			.grammar
@@ -173,9 +428,11 @@ impl<'a, M: KindsMetaInfo + 'static> Generator<'a, M> {
 		let node_display: Vec<_> = node_kinds.iter().collect();
 		let node_kinds: Vec<_> = node_kinds.iter().map(|x| format_ident!("{}", x)).collect();
 
+		let attributes = self.options.backend.kind_attributes();
+		let language = self.options.backend.kind_items();
+
 		let def = quote! {
-			#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, cstree::Syntax)]
-			#[repr(u32)]
+			#attributes
 			pub enum SyntaxKind {
 				/// Terminal tokens
 				#(#token_kinds,)*
@@ -186,6 +443,33 @@ impl<'a, M: KindsMetaInfo + 'static> Generator<'a, M> {
 				#[doc(hidden)]
 				Eof,
 			}
+
+			#language
+
+			impl SyntaxKind {
+				/// Tokens that can be anywhere in the tree, the grammar doesn't mention them (ex: whitespace, comments)
+				pub fn is_trivia(self) -> bool { #is_trivia }
+
+				/// Tokens the lexer couldn't make sense of
+				pub fn is_error(self) -> bool { #is_error }
+
+				pub fn is_keyword(self) -> bool { #is_keyword }
+
+				pub fn is_punct(self) -> bool { #is_punct }
+
+				pub fn is_literal(self) -> bool { #is_literal }
+
+				pub fn is_ident(self) -> bool { #is_ident }
+
+				/// Left and right binding powers of the kind as an infix operator, for Pratt parsing
+				pub fn infix_binding_power(self) -> Option<(u8, u8)> { #infix }
+
+				/// Binding power of the kind as a prefix operator on its operand
+				pub fn prefix_binding_power(self) -> Option<u8> { #prefix }
+
+				/// Binding power of the kind as a postfix operator on its operand
+				pub fn postfix_binding_power(self) -> Option<u8> { #postfix }
+			}
 		};
 
 		let display = quote! {
@@ -201,6 +485,21 @@ impl<'a, M: KindsMetaInfo + 'static> Generator<'a, M> {
 		};
 
 
+		// The parser runtime of `ungrammar_extra::parser` works with any kind implementing it
+		let tree_kind = quote! {
+			impl ungrammar_extra::TreeKind for SyntaxKind {
+				fn is_trivia(self) -> bool { SyntaxKind::is_trivia(self) }
+
+				fn description(self) -> &'static str {
+					match self {
+						#(Self::#token_kinds => #token_descriptions,)*
+						#(Self::#node_kinds => #node_display,)*
+						Self::Eof => "end of input",
+					}
+				}
+			}
+		};
+
 		let from = quote! {
 			impl From<TokenKind> for SyntaxKind {
 				fn from(kind: TokenKind) -> Self {
@@ -211,16 +510,16 @@ impl<'a, M: KindsMetaInfo + 'static> Generator<'a, M> {
 			}
 		};
 
-		def.to_string() + "\n\n" + &display.to_string() + "\n\n" + &from.to_string()
+		def.to_string() + "\n\n" + &display.to_string() + "\n\n" + &tree_kind.to_string() + "\n\n" + &from.to_string()
 	}
 
 	fn gen_tokens(&mut self) -> String {
-		self.grammar
-			.tokens()
-			.map(|n| {
+		let text = self.options.token_text();
+
+		self.token_kinds()
+			.map(|kind| {
 				{
-				
-					let ident = format_ident!("{}", map_token::<M>(&self.grammar[n].name).1);
+					let ident = format_ident!("{}", kind.name);
 
 					quote! {
 						#[derive(Clone, PartialEq, Eq, Hash)]
@@ -233,7 +532,7 @@ impl<'a, M: KindsMetaInfo + 'static> Generator<'a, M> {
 						}
 
 						impl AstToken for #ident {
-							fn text(&self) -> Text { unsafe { std::mem::transmute(self.0.text_key().unwrap()) } }
+							#text
 						}
 
 						impl AstElement for #ident {
@@ -246,7 +545,7 @@ impl<'a, M: KindsMetaInfo + 'static> Generator<'a, M> {
 								Self::can_cast(tok.kind()).then(|| Self(tok))
 							}
 
-							fn span(&self) -> FileSpan { blanket_impls::default_span(&self.0) }
+							fn span(&self) -> Span { blanket_impls::token_span(&self.0) }
 
 							fn inner(self) -> SyntaxElement { self.0.into() }
 						}
@@ -276,92 +575,197 @@ impl<'a, M: KindsMetaInfo + 'static> Generator<'a, M> {
 	}
 
 	pub fn gen_nodes(&mut self) -> Vec<NodeData> {
-		const HANDWRITTEN: &[&str] = &["TokenTree"];
+		let generated: Vec<_> = self.grammar.iter().filter(|x| !self.options.is_handwritten(&self.grammar[*x].name)).collect();
 
-		self.grammar
-			.iter()
-			.filter(|x| !HANDWRITTEN.contains(&self.grammar[*x].name.as_str()))
-			.map(|x| {
+		generated
+			.into_iter()
+			.flat_map(|x| {
 				let node = &self.grammar[x];
 				match self.node_types[&x] {
 					NodeType::Struct => {
 						let mut s = Struct {
 							name: node.name.clone(),
+							node: x,
 							fields: Vec::new(),
 							type_cardinality: HashMap::new(),
 						};
-						self.lower_rule(&mut s, None, &node.rule);
-						NodeData::Struct(s)
+						self.lower_rule(&mut s, None, &node.rule, false);
+						self.check_spans(&s, &node.rule);
+
+						// The enums of its alternations go right after the struct
+						let inline = std::mem::take(&mut self.inline_enums);
+						std::iter::once(NodeData::Struct(s)).chain(inline.into_iter().map(NodeData::Enum)).collect()
 					},
 					NodeType::Enum => {
-						let e = self.lower_enum(node.name.clone(), &node.rule);
-						NodeData::Enum(e)
+						let e = self.lower_enum(&node.name, node.name.clone(), &node.rule);
+						vec![NodeData::Enum(e)]
 					},
 				}
 			})
 			.collect()
 	}
 
-	fn lower_rule(&mut self, out: &mut Struct, label: Option<&String>, rule: &Rule) {
-		if self.lower_comma_list(out, label, rule) {
-			return;
-		}
-
+	fn lower_rule(&mut self, out: &mut Struct, label: Option<&String>, rule: &Rule, optional: bool) {
 		match rule {
-			Rule::Labeled { label, rule } => self.lower_rule(out, Some(label), rule),
+			Rule::Labeled { label, rule } => self.lower_rule(out, Some(label), rule, optional),
 			Rule::Node(node) => {
 				let ty = self.grammar[*node].name.clone();
-				let index = out.get_cardinality(&ty, rule, self.grammar);
+				let Some(index) = self.cardinality(out, &ty, rule) else { return };
 
 				out.fields.push(Field::Node {
 					name: label.cloned().unwrap_or_else(|| to_snake_case(&ty)),
 					ty,
 					cardinality: Cardinality::One(index),
+					optional,
 				});
 			},
 			Rule::Token(tok) => {
-				let tok = &self.grammar[*tok].name;
-				let ty = map_token::<M>(tok).1;
-				let index = out.get_cardinality(&ty, rule, self.grammar);
+				let Some(ty) = self.map_token(&out.name, rule, *tok) else { return };
+				let Some(index) = self.cardinality(out, &ty, rule) else { return };
 
 				out.fields.push(Field::Token {
 					name: label.cloned().unwrap_or_else(|| to_snake_case(&ty)),
 					ty,
 					cardinality: Cardinality::One(index),
+					optional,
 				});
 			},
 			Rule::Seq(rules) => {
-				for rule in rules {
-					self.lower_rule(out, label, rule);
+				// A separated list can be anywhere in the sequence (ex: `'(' Param (',' Param)* ')'`)
+				let mut rest = rules.as_slice();
+				while let Some(first) = rest.first() {
+					let len = match separated_list(rest) {
+						Some((node, separator, len)) => {
+							self.lower_separated_list(out, label, rule, node, separator, optional);
+							len
+						},
+						None => {
+							self.lower_rule(out, label, first, optional);
+							1
+						},
+					};
+					rest = &rest[len..];
 				}
 			},
-			Rule::Alt(_) => panic!(
-				"rule `{}` is not allowed in this position",
-				format_rule(rule, self.grammar)
-			),
-			Rule::Opt(rule) => self.lower_rule(out, label, rule),
-			Rule::Rep(rule) => {
-				if let Rule::Node(node) = &**rule {
-					let ty = self.grammar[*node].name.clone();
-					out.use_many_cardinality(&ty, rule, self.grammar);
+			Rule::Alt(_) => {
+				let Some(ty) = self.lower_inline_enum(out, label, rule) else { return };
+				let Some(index) = self.cardinality(out, &ty, rule) else { return };
 
-					out.fields.push(Field::Node {
-						name: label.cloned().unwrap_or_else(|| pluralize(&to_snake_case(&ty))),
-						ty,
-						cardinality: Cardinality::Many,
-					});
-				}
+				out.fields.push(Field::Node {
+					name: label.cloned().unwrap_or_else(|| to_snake_case(&ty)),
+					ty,
+					cardinality: Cardinality::One(index),
+					optional,
+				});
 			},
+			Rule::Opt(rule) => self.lower_rule(out, label, rule, true),
+			Rule::Rep(inner) => self.lower_rep(out, label, rule, inner),
+		}
+	}
+
+	/// Lowers the repeated rule of `rep` into a `Many` field, or a group for a repeated sequence,
+	/// shapes that can't be iterated over are reported instead of being left out
+	fn lower_rep(&mut self, out: &mut Struct, label: Option<&String>, rep: &Rule, rule: &Rule) {
+		let ty = match rule {
+			Rule::Labeled { label, rule } => return self.lower_rep(out, Some(label), rep, rule),
+			Rule::Node(node) => self.grammar[*node].name.clone(),
+			Rule::Token(tok) => {
+				let Some(ty) = self.map_token(&out.name, rule, *tok) else { return };
+				ty
+			},
+			Rule::Alt(_) => {
+				let Some(ty) = self.lower_inline_enum(out, label, rule) else { return };
+				ty
+			},
+			Rule::Seq(rules) => return self.lower_group(out, label, rep, rules),
+			Rule::Opt(_) | Rule::Rep(_) => return self.report(&out.name, rep, DiagnosticKind::UnsupportedRepetition),
+		};
+
+		if let Err(kind) = out.use_many_cardinality(&ty) {
+			return self.report(&out.name, rep, kind);
 		}
+
+		let name = label.cloned().unwrap_or_else(|| pluralize(&to_snake_case(&ty)));
+		out.fields.push(match rule {
+			Rule::Token(_) => Field::Token { name, ty, cardinality: Cardinality::Many, optional: true },
+			_ => Field::Node { name, ty, cardinality: Cardinality::Many, optional: true },
+		});
 	}
 
-	fn lower_enum(&mut self, name: String, rule: &Rule) -> Enum {
+	/// Lowers a repeated sequence of nodes and tokens (ex: `(',' Param)*`) into a group field
+	fn lower_group(&mut self, out: &mut Struct, label: Option<&String>, rep: &Rule, rules: &[Rule]) {
+		let mut items = vec![];
+
+		for rule in rules {
+			let (item_label, item) = match rule {
+				Rule::Labeled { label, rule } => (Some(label), &**rule),
+				rule => (None, rule),
+			};
+
+			match item {
+				Rule::Node(node) => {
+					let ty = self.grammar[*node].name.clone();
+					let name = item_label.cloned().unwrap_or_else(|| to_snake_case(&ty));
+					items.push(Field::Node { name, ty, cardinality: Cardinality::One(0), optional: false });
+				},
+				Rule::Token(tok) => {
+					let Some(ty) = self.map_token(&out.name, item, *tok) else { return };
+					let name = item_label.cloned().unwrap_or_else(|| to_snake_case(&ty));
+					items.push(Field::Token { name, ty, cardinality: Cardinality::One(0), optional: false });
+				},
+				_ => return self.report(&out.name, rep, DiagnosticKind::UnsupportedRepetition),
+			}
+		}
+
+		// Tuples start at an element of the first type, so the group takes every one of them, the other
+		// types can be used before (ex: `first:Literal rest:('+' Literal)*`), but not after it
+		let Some((first, rest)) = items.split_first() else { return };
+		if let Err(kind) = out.use_many_cardinality(first.ty()) {
+			return self.report(&out.name, rep, kind);
+		}
+		for item in rest {
+			out.type_cardinality.insert(item.ty().to_owned(), Cardinality::Many);
+		}
+
+		let name = label.cloned().unwrap_or_else(|| {
+			pluralize(&items.iter().map(|item| item.name()).collect::<Vec<_>>().join("_"))
+		});
+		out.fields.push(Field::Group { name, items });
+	}
+
+	/// Makes an enum for an alternation inside a struct rule, named after the struct and the label
+	/// (ex: `BinExprOp` for `BinExpr = lhs:Expr op:('+' | '-') rhs:Expr`), returns its name
+	fn lower_inline_enum(&mut self, out: &Struct, label: Option<&String>, rule: &Rule) -> Option<String> {
+		let Some(label) = label else {
+			self.report(&out.name, rule, DiagnosticKind::UnlabeledAlternation);
+			return None;
+		};
+
+		let name = format!("{}{}", out.name, to_pascal_case(label));
+		let taken = self.grammar.iter().any(|node| self.grammar[node].name == name)
+			|| self.inline_enums.iter().any(|e| e.name == name);
+		if taken {
+			self.report(&out.name, rule, DiagnosticKind::DuplicateType { ty: name });
+			return None;
+		}
+
+		let e = self.lower_enum(&out.name, name.clone(), rule);
+		self.inline_enums.push(e);
+
+		Some(name)
+	}
+
+	/// Lowers an alternation into an enum, problems are reported on the grammar `node` it's in
+	fn lower_enum(&mut self, node: &str, name: String, rule: &Rule) -> Enum {
 		let mut node_variants = Vec::new();
 		let mut token_variants = Vec::new();
 
 		let alt = match rule {
-			Rule::Alt(alt) => alt,
-			_ => panic!("expected an alt rule"),
+			Rule::Alt(alt) => alt.as_slice(),
+			_ => {
+				self.report(node, rule, DiagnosticKind::ExpectedAlternation);
+				&[]
+			},
 		};
 
 		for alt in alt {
@@ -374,13 +778,11 @@ impl<'a, M: KindsMetaInfo + 'static> Generator<'a, M> {
 					});
 				},
 				Rule::Token(tok) => {
-					let tok = map_token::<M>(&self.grammar[*tok].name).1;
-					token_variants.push(tok);
+					if let Some(tok) = self.map_token(node, alt, *tok) {
+						token_variants.push(tok);
+					}
 				},
-				_ => panic!(
-					"rule `{}` is not allowed in this position",
-					format_rule(rule, self.grammar)
-				),
+				_ => self.report(node, alt, DiagnosticKind::UnexpectedRule),
 			}
 		}
 
@@ -391,73 +793,100 @@ impl<'a, M: KindsMetaInfo + 'static> Generator<'a, M> {
 		}
 	}
 
-	// (T (',' T)* ','?)
-	// Stolen from rust-analyzer
-	fn lower_comma_list(&mut self, out: &mut Struct, label: Option<&String>, r: &Rule) -> bool {
-		let rule = match r {
-			Rule::Seq(it) => it,
-			_ => return false,
-		};
-		let (node, repeat, trailing_comma) = match rule.as_slice() {
-			[Rule::Node(node), Rule::Rep(repeat), Rule::Opt(trailing_comma)] => (node, repeat, trailing_comma),
-			_ => return false,
-		};
-		let repeat = match &**repeat {
-			Rule::Seq(it) => it,
-			_ => return false,
-		};
-		match repeat.as_slice() {
-			[comma, Rule::Node(n)] if comma == &**trailing_comma && n == node => (),
-			_ => return false,
-		}
-		let ty = self.grammar[*node].name.clone();
-		let name = label.cloned().unwrap_or_else(|| pluralize(&to_snake_case(&ty)));
-		out.use_many_cardinality(&ty, r, self.grammar);
+	/// Lowers a list of nodes with separators, which is only optional when the grammar says so,
+	/// otherwise it has at least one element
+	fn lower_separated_list(&mut self, out: &mut Struct, label: Option<&String>, r: &Rule, node: Node, separator: &Rule, optional: bool) {
+		let Rule::Token(token) = separator else { unreachable!("separators are tokens") };
 
-		out.fields.push(Field::Node {
-			name,
-			ty,
-			cardinality: Cardinality::Many,
-		});
+		let ty = self.grammar[node].name.clone();
+		let name = label.cloned().unwrap_or_else(|| pluralize(&to_snake_case(&ty)));
+		if let Err(kind) = out.use_many_cardinality(&ty) {
+			return self.report(&out.name, r, kind);
+		}
+		let Some(separator) = self.map_token(&out.name, separator, *token) else { return };
+		if let Err(kind) = out.use_many_cardinality(&separator) {
+			return self.report(&out.name, r, kind);
+		}
 
-		true
+		out.fields.push(Field::Separated { name, ty, separator, optional });
 	}
-}
 
-fn map_token<M: KindsMetaInfo>(token: &str) -> (String, String) {
-	let Some((lit, desc)) = M::kinds().get_key_value(token) else {
-		let mut message = format!("No token kind information found for literal '{token}'\n");
-		message += "Available literals are:\n";
+	/// Kind name of the token, the problem is reported if there's no kind information for it
+	fn map_token(&mut self, node: &str, rule: &Rule, token: Token) -> Option<String> {
+		let literal = &self.grammar[token].name;
 
-		for (lit, desc) in M::kinds() {
-			message += &*format!("  - {lit} ({desc})\n");
+		if let Some(kind) = self.meta.get(literal) {
+			return Some(kind.name.clone());
 		}
 
-		panic!("{}", message)
-	};
+		let position = self.source.as_ref().and_then(|source| {
+			let (definition, occurrence) = self.token_occurrence(rule, token)?;
+			source.locate_token(&self.grammar[definition].name, literal, occurrence)
+		});
+		let suggestions = similar(literal, self.meta.iter().map(|kind| kind.literal.as_str()));
 
-	(lit.to_string(), desc.to_string())
-}
+		self.report(node, rule, DiagnosticKind::UnknownToken { literal: literal.clone(), position, suggestions });
+		None
+	}
+
+	/// Node of the grammar whose rule has the token rule, along with how many uses of the same token
+	/// come before it, which is the order they're written in
+	fn token_occurrence(&self, rule: &Rule, token: Token) -> Option<(Node, usize)> {
+		fn find(r: &Rule, rule: &Rule, token: Token, before: &mut usize) -> bool {
+			if std::ptr::eq(r, rule) { return true }
 
-/*
-fn map_token<M: KindsMetaInfo>(token: &TokenData) -> (String, String) {
-	// TODO: Take the parent rule as parameter, if the parent is given the panic
-	//       token information can tell who is the parent and maybe also
-	//       at which line/position it is.
-	//       Optionally also get the `token` parameter full information for more accuracy
+			match r {
+				Rule::Token(t) => {
+					*before += (*t == token) as usize;
+					false
+				},
+				Rule::Labeled { rule: r, .. } | Rule::Opt(r) | Rule::Rep(r) => find(r, rule, token, before),
+				Rule::Seq(rules) | Rule::Alt(rules) => rules.iter().any(|r| find(r, rule, token, before)),
+				Rule::Node(_) => false,
+			}
+		}
 
-	let Some((lit, desc)) = M::kinds().get_key_value(&*token.name) else {
-		let mut message = format!("No token kind information found for literal '{}'\n", token.name);
-		message += &*format!("Token is at line {}", token.);
-		message += "Available literals are:\n";
+		self.grammar.iter().find_map(|node| {
+			let mut before = 0;
+			find(&self.grammar[node].rule, rule, token, &mut before).then_some((node, before))
+		})
+	}
+
+	fn cardinality(&mut self, out: &mut Struct, ty: &String, rule: &Rule) -> Option<usize> {
+		out.get_cardinality(ty).map_err(|kind| self.report(&out.name, rule, kind)).ok()
+	}
 
-		for (lit, desc) in M::kinds() {
-			message += &*format!("  - {lit} ({desc})");
+	/// Reports the optional fields that can't be told apart from another of their type in the tree
+	fn check_spans(&mut self, s: &Struct, rule: &Rule) {
+		let mut reported = vec![];
+		for index in 0..s.fields.len() {
+			let Some(span) = s.span(index) else { continue };
+			let ty = s.fields[index].ty();
+			if span.optional.len() > 1 && !reported.contains(&ty) {
+				reported.push(ty);
+				self.report(&s.name, rule, DiagnosticKind::AmbiguousOptional { ty: ty.to_owned() });
+			}
 		}
+	}
 
-		panic!("{}", message)
-	};
+	fn report(&mut self, node: &str, rule: &Rule, kind: DiagnosticKind) {
+		self.diagnostics.push(Diagnostic {
+			node: node.to_owned(),
+			rule: format_rule(rule, self.grammar).to_string(),
+			kind,
+		});
+	}
+}
 
-	(lit.to_string(), desc.to_string())
+/// The node and separator of a `T (',' T)*` list at the start of the rules of a sequence, with or
+/// without a trailing separator, along with how many rules it takes, it's lowered to a single `Separated` field
+// Stolen from rust-analyzer
+pub(crate) fn separated_list(rules: &[Rule]) -> Option<(Node, &Rule, usize)> {
+	let (Rule::Node(node), Rule::Rep(repeat)) = (rules.first()?, rules.get(1)?) else { return None };
+	let Rule::Seq(repeat) = &**repeat else { return None };
+	let [separator @ Rule::Token(_), Rule::Node(n)] = repeat.as_slice() else { return None };
+	if n != node { return None }
+
+	let trailing = matches!(rules.get(2), Some(Rule::Opt(trailing)) if **trailing == *separator);
+	Some((*node, separator, 2 + trailing as usize))
 }
-*/
\ No newline at end of file
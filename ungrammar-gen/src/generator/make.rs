@@ -0,0 +1,138 @@
+// Standard Uses
+
+// Crate Uses
+use super::{
+	gen::{Cardinality, Field, Generator, NodeData, Struct},
+	utils::{io::{GeneratedFile, Uses}, lint::to_snake_case},
+};
+
+// External Uses
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+
+
+impl Generator<'_> {
+	/// Generates the `make` module, with a function that builds every token from its text
+	/// and every struct node from its fields, in the order the grammar lists them, on cstree
+	/// the nodes are a `Result` since children from a tree without a resolver have no text
+	pub(crate) fn gen_make(&self, nodes: &[NodeData]) -> GeneratedFile {
+		let tokens = self.token_kinds().map(|kind| {
+			let ty = format_ident!("{}", kind.name);
+			let name = format_ident!("{}", to_snake_case(&kind.name));
+
+			quote! {
+				pub fn #name(text: &str) -> #ty {
+					#ty::cast(build::token(SyntaxKind::#ty, text).into()).unwrap()
+				}
+			}
+		});
+
+		let structs = nodes.iter().filter_map(|node| match node {
+			NodeData::Struct(s) => Some(make_struct(self, s)),
+			NodeData::Enum(_) => None,
+		});
+
+		let content = quote! {
+			#(#tokens)*
+
+			#(#structs)*
+		};
+
+		GeneratedFile {
+			name: "make.rs",
+			uses: Some(Uses {
+				mods: vec![],
+				std: vec![],
+				krate: vec!["super::{*, ast::*, token::*}".to_owned()],
+				external: vec![],
+			}),
+			content: self.options.backend.make_items().to_owned() + "\n" + &content.to_string(),
+		}
+	}
+}
+
+fn make_struct(generator: &Generator, s: &Struct) -> TokenStream {
+	let ty = format_ident!("{}", s.name);
+	let name = format_ident!("{}", to_snake_case(&s.name));
+
+	let (params, children): (Vec<_>, Vec<_>) = s.fields
+		.iter()
+		.map(|field| {
+			let (Field::Node { name, ty, cardinality, .. } | Field::Token { name, ty, cardinality, .. }) = field else {
+				return match field {
+					Field::Separated { .. } => make_separated(generator, field),
+					_ => make_group(field),
+				};
+			};
+			let (name, ty) = (format_ident!("{}", name), format_ident!("{}", ty));
+
+			match cardinality {
+				Cardinality::One(_) if field.is_optional() => (
+					quote! { #name: Option<#ty> },
+					quote! { #name.map(AstElement::inner) },
+				),
+				Cardinality::One(_) => (quote! { #name: #ty }, quote! { Some(#name.inner()) }),
+				Cardinality::Many => (
+					quote! { #name: impl IntoIterator<Item = #ty> },
+					quote! { #name.into_iter().map(AstElement::inner) },
+				),
+			}
+		})
+		.unzip();
+
+	let (output, body) = generator.options.backend.make_node(&ty);
+
+	quote! {
+		pub fn #name(#(#params),*) -> #output {
+			let children: Vec<SyntaxElement> = std::iter::empty()
+				#(.chain(#children))*
+				.collect();
+
+			#body
+		}
+	}
+}
+
+/// Parameter and children of a group field, its tuples are laid out one after the other
+fn make_group(field: &Field) -> (TokenStream, TokenStream) {
+	let Field::Group { name, items } = field else { unreachable!("only groups have items") };
+
+	let name = format_ident!("{}", name);
+	let types: Vec<_> = items.iter().map(|item| format_ident!("{}", item.ty())).collect();
+	let vars: Vec<_> = (0..items.len()).map(|i| format_ident!("x{}", i)).collect();
+
+	(
+		quote! { #name: impl IntoIterator<Item = (#(#types),*)> },
+		quote! { #name.into_iter().flat_map(|(#(#vars),*)| [#(#vars.inner()),*]) },
+	)
+}
+
+/// Parameter and children of a separated list, the elements are given and the separators put between them
+fn make_separated(generator: &Generator, field: &Field) -> (TokenStream, TokenStream) {
+	let Field::Separated { name, ty, separator, optional } = field else { unreachable!("only lists have separators") };
+	let literal = generator
+		.token_kinds()
+		.find(|kind| &kind.name == separator)
+		.map(|kind| kind.literal.clone())
+		.expect("separators are tokens of the grammar");
+
+	let ty = format_ident!("{}", ty);
+	let separator = format_ident!("{}", to_snake_case(separator));
+	let separated = |elements: TokenStream| quote! {
+		#elements.into_iter().enumerate().flat_map(|(i, x)| {
+			(i > 0).then(|| #separator(#literal).inner()).into_iter().chain([x.inner()])
+		})
+	};
+
+	// A list the grammar requires to have an element is given its first one apart from the rest
+	if !optional {
+		let (first, rest) = (format_ident!("{}_first", name), format_ident!("{}_rest", name));
+		let children = separated(quote! { std::iter::once(#first).chain(#rest) });
+
+		return (quote! { #first: #ty, #rest: impl IntoIterator<Item = #ty> }, children);
+	}
+
+	let name = format_ident!("{}", name);
+	(quote! { #name: impl IntoIterator<Item = #ty> }, separated(quote! { #name }))
+}
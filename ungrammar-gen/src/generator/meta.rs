@@ -0,0 +1,160 @@
+// Standard Uses
+#[cfg(feature = "cli")]
+use std::path::Path;
+
+// External Uses
+#[cfg(feature = "cli")]
+use eyre::{bail, eyre, Context, Result};
+#[cfg(feature = "cli")]
+use serde::Deserialize;
+use ungrammar_extra::{KindsMetaInfo, SyntaxKindMeta};
+#[cfg(feature = "cli")]
+use ungrammar_extra::{Assoc, Fixity, Operator, TokenCategory};
+
+
+
+/// Token kinds information the generator works with, it either comes from a type
+/// implementing [`KindsMetaInfo`] or from a kinds file given at runtime (see [`KindsMeta::from_path`])
+pub struct KindsMeta {
+	kinds: Vec<SyntaxKindMeta>,
+	pub token_kind: Option<String>,
+}
+
+impl KindsMeta {
+	pub fn from_info<M: KindsMetaInfo>() -> Self {
+		let kinds = M::literals()
+			.iter()
+			.map(|lit| SyntaxKindMeta {
+				name: M::kinds()[lit].to_owned(),
+				literal: lit.to_string(),
+				description: M::descriptions().get(lit).unwrap_or(&M::kinds()[lit]).to_string(),
+				trivia: M::is_trivia(lit),
+				error: M::is_error(lit),
+				category: M::category(lit),
+				operators: M::operators_of(lit),
+			})
+			.collect();
+
+		Self { kinds, token_kind: None }
+	}
+
+	pub fn from_kinds(kinds: Vec<SyntaxKindMeta>) -> Self { Self { kinds, token_kind: None } }
+
+	/// Reads a kinds file (needs the `cli` feature), which is a toml file in the shape of:
+	/// ```toml
+	/// token_kind = "crate::lexer::TokenKind"
+	///
+	/// [[kind]]
+	/// lit = "+"
+	/// name = "PlusOp"
+	/// desc = "Plus"
+	/// category = "punct"
+	/// infix = { prec = 10, assoc = "left" }
+	///
+	/// [[kind]]
+	/// lit = "whitespace"
+	/// name = "Whitespace"
+	/// trivia = true
+	/// ```
+	#[cfg(feature = "cli")]
+	pub fn from_path(path: &Path) -> Result<Self> {
+		let file = std::fs::read_to_string(path)
+			.with_context(|| format!("Couldn't read kinds file at path '{}'", path.display()))?;
+
+		Self::from_toml(&file).with_context(|| format!("Kinds file at path '{}' is not valid", path.display()))
+	}
+
+	#[cfg(feature = "cli")]
+	pub fn from_toml(text: &str) -> Result<Self> {
+		let file: KindsFile = toml::from_str(text)?;
+
+		let kinds = file
+			.kinds
+			.into_iter()
+			.map(|kind| {
+				let operators = kind.operators().with_context(|| format!("Kind '{}' is not a valid operator", kind.name))?;
+				let category = kind
+					.category
+					.map(|name| {
+						TokenCategory::from_name(&name).ok_or_else(|| {
+							eyre!("Kind '{}' has an unknown category '{name}', expected one of keyword, punct, literal or ident", kind.name)
+						})
+					})
+					.transpose()?;
+
+				Ok(SyntaxKindMeta {
+					description: kind.desc.unwrap_or_else(|| kind.name.clone()),
+					name: kind.name,
+					literal: kind.lit,
+					trivia: kind.trivia,
+					error: kind.error,
+					category,
+					operators,
+				})
+			})
+			.collect::<Result<_>>()?;
+
+		Ok(Self { kinds, token_kind: file.token_kind })
+	}
+
+	pub fn get(&self, literal: &str) -> Option<&SyntaxKindMeta> { self.kinds.iter().find(|k| k.literal == literal) }
+
+	pub fn iter(&self) -> impl Iterator<Item = &SyntaxKindMeta> { self.kinds.iter() }
+}
+
+#[cfg(feature = "cli")]
+#[derive(Deserialize)]
+struct KindsFile {
+	token_kind: Option<String>,
+	#[serde(rename = "kind", default)]
+	kinds: Vec<KindEntry>,
+}
+
+#[cfg(feature = "cli")]
+#[derive(Deserialize)]
+struct KindEntry {
+	lit: String,
+	name: String,
+	desc: Option<String>,
+	#[serde(default)]
+	trivia: bool,
+	#[serde(default)]
+	error: bool,
+	category: Option<String>,
+	infix: Option<OperatorEntry>,
+	prefix: Option<OperatorEntry>,
+	postfix: Option<OperatorEntry>,
+}
+
+#[cfg(feature = "cli")]
+impl KindEntry {
+	fn operators(&self) -> Result<Vec<Operator>> {
+		let mut operators = vec![];
+
+		if let Some(infix) = &self.infix {
+			let assoc = match infix.assoc.as_deref() {
+				None => Assoc::Left,
+				Some(name) => Assoc::from_name(name)
+					.ok_or_else(|| eyre!("Unknown associativity '{name}', expected left or right"))?,
+			};
+			operators.push((Fixity::Infix(assoc), infix.prec));
+		}
+		operators.extend(self.prefix.as_ref().map(|prefix| (Fixity::Prefix, prefix.prec)));
+		operators.extend(self.postfix.as_ref().map(|postfix| (Fixity::Postfix, postfix.prec)));
+
+		operators
+			.into_iter()
+			.map(|(fixity, prec)| match prec {
+				u8::MAX => bail!("Precedence can't be {}, it has to leave room for associativity", u8::MAX),
+				prec => Ok(Operator::new(fixity, prec)),
+			})
+			.collect()
+	}
+}
+
+#[cfg(feature = "cli")]
+#[derive(Deserialize)]
+struct OperatorEntry {
+	prec: u8,
+	assoc: Option<String>,
+}
@@ -1,19 +1,29 @@
 // Relative Modules
 mod ast;
+mod backend;
+mod error;
 mod gen;
+mod meta;
 mod nodes;
+mod visitor;
+mod make;
+mod printer;
 mod blanket_impls;
 
-mod utils;
+pub(crate) mod utils;
 
 // Standard Uses
-use std::path::Path;
+use std::{collections::BTreeMap, path::Path};
 
 // Crate Uses
-use crate::generator::gen::Generator;
+pub use crate::generator::{
+	backend::{Backend, CustomType, GeneratorOptions, HandwrittenNode, NodeHook},
+	error::{Diagnostic, DiagnosticKind, GenError, Position},
+	gen::Generator,
+	meta::KindsMeta,
+};
 
 // External Uses
-use eyre::{Context, Result};
 use ungrammar::Grammar;
 use ungrammar_extra::KindsMetaInfo;
 
@@ -21,18 +31,96 @@ use ungrammar_extra::KindsMetaInfo;
 
 pub fn from_path<M: KindsMetaInfo + 'static, K>(
 	grammar_path: &Path, output_path: &Path
-) -> Result<()> {
-	let file = std::fs::read_to_string(grammar_path)
-		.with_context(|| format!("Couldn't read file at path '{}'", grammar_path.display()))?;
-	let grammar: Grammar = file.parse()?;
+) -> Result<(), GenError> {
+	from_path_with_meta(
+		grammar_path, output_path,
+		&KindsMeta::from_info::<M>(), core::any::type_name::<K>(), &GeneratorOptions::default()
+	)
+}
+
+/// Same as [`from_path`], but with the token kinds information given at runtime,
+/// `token_kind_namespace` is the path the generated code uses to refer to the token kind enum
+pub fn from_path_with_meta(
+	grammar_path: &Path, output_path: &Path,
+	meta: &KindsMeta, token_kind_namespace: &str, options: &GeneratorOptions
+) -> Result<(), GenError> {
+	let (file, grammar) = read_grammar(grammar_path)?;
 
 	if !output_path.exists() {
 		println!("Creating generated code path at {}", output_path.display());
-		std::fs::create_dir_all(output_path)?;
+		std::fs::create_dir_all(output_path)
+			.map_err(|source| GenError::Write { path: output_path.to_owned(), source })?;
 	}
 
-	Generator::<M>::new(&grammar, core::any::type_name::<K>().to_owned())
-		.generate(&output_path);
+	Generator::new(&grammar, meta, token_kind_namespace.to_owned())
+		.with_source(&file)
+		.with_options(options.clone())
+		.generate(output_path)
+}
+
+/// Checks that the code in `output_path` is what [`from_path`] would generate, without
+/// writing anything, returns [`GenError::Stale`] with the files that are missing or differ
+pub fn check<M: KindsMetaInfo + 'static, K>(
+	grammar_path: &Path, output_path: &Path
+) -> Result<(), GenError> {
+	check_with_meta(
+		grammar_path, output_path,
+		&KindsMeta::from_info::<M>(), core::any::type_name::<K>(), &GeneratorOptions::default()
+	)
+}
+
+/// Same as [`check`], but with the token kinds information given at runtime
+pub fn check_with_meta(
+	grammar_path: &Path, output_path: &Path,
+	meta: &KindsMeta, token_kind_namespace: &str, options: &GeneratorOptions
+) -> Result<(), GenError> {
+	let (file, grammar) = read_grammar(grammar_path)?;
+
+	Generator::new(&grammar, meta, token_kind_namespace.to_owned())
+		.with_source(&file)
+		.with_options(options.clone())
+		.check(output_path)
+}
+
+/// Generates the code without writing it, see [`Generator::generate_to_memory`]
+pub fn to_memory<M: KindsMetaInfo + 'static, K>(
+	grammar_path: &Path, output_path: &Path
+) -> Result<BTreeMap<String, String>, GenError> {
+	to_memory_with_meta(
+		grammar_path, output_path, &KindsMeta::from_info::<M>(), core::any::type_name::<K>(), &GeneratorOptions::default()
+	)
+}
+
+/// Same as [`to_memory`], but with the token kinds information given at runtime
+pub fn to_memory_with_meta(
+	grammar_path: &Path, output_path: &Path,
+	meta: &KindsMeta, token_kind_namespace: &str, options: &GeneratorOptions
+) -> Result<BTreeMap<String, String>, GenError> {
+	let (file, grammar) = read_grammar(grammar_path)?;
+
+	Generator::new(&grammar, meta, token_kind_namespace.to_owned())
+		.with_source(&file)
+		.with_options(options.clone())
+		.generate_to_memory(output_path)
+}
+
+/// Generates the code as a single unformatted source with every module inlined, for when
+/// it's consumed as tokens (ex: by a macro), see [`Generator::generate_source`]
+pub fn to_source_with_meta(
+	grammar_path: &Path, meta: &KindsMeta, token_kind_namespace: &str, options: &GeneratorOptions
+) -> Result<String, GenError> {
+	let (file, grammar) = read_grammar(grammar_path)?;
+
+	Generator::new(&grammar, meta, token_kind_namespace.to_owned())
+		.with_source(&file)
+		.with_options(options.clone())
+		.generate_source()
+}
+
+pub(crate) fn read_grammar(grammar_path: &Path) -> Result<(String, Grammar), GenError> {
+	let file = std::fs::read_to_string(grammar_path)
+		.map_err(|source| GenError::Read { path: grammar_path.to_owned(), source })?;
+	let grammar = file.parse().map_err(GenError::Parse)?;
 
-	Ok(())
+	Ok((file, grammar))
 }
@@ -1,68 +1,80 @@
-// Standard Uses
-use std::path::Path;
-
 // Crate Uses
-use super::utils::io::{self, Uses};
+use super::utils::io::{GeneratedFile, Uses};
 use crate::generator::gen::Generator;
 
-// External Uses
-use ungrammar_extra::KindsMetaInfo;
 
 
+impl Generator<'_> {
+	pub(crate) fn gen_ast_traits(&self) -> GeneratedFile {
+		let backend = self.options.backend;
 
-impl<'a, M: KindsMetaInfo + 'static> Generator<'a, M> {
-	pub(crate) fn gen_ast_traits(&self, out: &Path) {
-		io::write_generated(
-			out,
-			"mod.rs",
-			Some(Uses { 
+		GeneratedFile {
+			name: "mod.rs",
+			uses: Some(Uses { 
                     mods: vec![
                         "token".to_string(), "kind".to_string(),
                         "ast".to_string(), //"nodes".to_string(), 
-                        "blanket_impls".to_string()
+                        "visitor".to_string(), "make".to_string(), "printer".to_string(), "blanket_impls".to_string()
                     ],
                     std: vec![],
-                    krate: vec!["kind::SyntaxKind".to_string()],
-                    external: vec!["text::Text".to_string(), "diagnostics::FileSpan".to_string()],
+                    krate: backend.traits_uses(),
+                    external: vec![],
                 
             }),
-			indoc::indoc! {r#"
-                pub type SyntaxNode = cstree::syntax::SyntaxNode<SyntaxKind>;
-                pub type SyntaxToken = cstree::syntax::SyntaxToken<SyntaxKind>;
-                pub type SyntaxElement = cstree::syntax::SyntaxElement<SyntaxKind>;
-                pub type SyntaxElementRef<'a> = cstree::syntax::SyntaxElementRef<'a, SyntaxKind>;
-                pub type SyntaxNodeChildren<'a> = cstree::syntax::SyntaxNodeChildren<'a, SyntaxKind>;
-
-                pub type ResolvedNode = cstree::syntax::ResolvedNode<SyntaxKind>;
-                pub type ResolvedToken = cstree::syntax::ResolvedToken<SyntaxKind>;
-                pub type ResolvedElement = cstree::syntax::ResolvedElement<SyntaxKind>;
+			content: backend.syntax_items().to_owned() + "\n" + &self.options.api_items() + indoc::indoc! {r#"
 
                 pub trait AstNode: Sized {}
 
-                pub trait AstToken: Sized {
-                    fn text(&self) -> Text;
-                }
-
                 pub trait AstElement: Sized {
                     fn can_cast(kind: SyntaxKind) -> bool;
 
                     fn cast(elem: SyntaxElement) -> Option<Self>;
 
-                    fn span(&self) -> FileSpan;
+                    fn span(&self) -> Span;
 
                     fn inner(self) -> SyntaxElement;
                 }
 
-                fn children<'a, T: 'a + AstElement>(node: &'a SyntaxNode) -> impl Iterator<Item = T> + 'a {
-                    node.children_with_tokens()
-                        .map(|x| match x {
-                            SyntaxElementRef::Node(node) => SyntaxElement::Node(node.clone()),
-                            SyntaxElementRef::Token(token) => SyntaxElement::Token(token.clone()),
+                /// Elements of a list along with the separators between them (ex: `a, b, c`)
+                #[derive(Clone)]
+                pub struct SeparatedList<T, Sep> {
+                    node: SyntaxNode,
+                    marker: std::marker::PhantomData<(T, Sep)>,
+                }
+
+                impl<T: AstElement, Sep: AstElement> SeparatedList<T, Sep> {
+                    pub fn new(node: &SyntaxNode) -> Self {
+                        Self { node: node.clone(), marker: std::marker::PhantomData }
+                    }
+
+                    pub fn elements(&self) -> impl Iterator<Item = T> + '_ { children(&self.node) }
+
+                    pub fn first(&self) -> Option<T> { self.elements().next() }
+
+                    pub fn separators(&self) -> impl Iterator<Item = Sep> + '_ { children(&self.node) }
+
+                    /// Each element with the separator after it, which only the last one can be without
+                    pub fn pairs(&self) -> impl Iterator<Item = (T, Option<Sep>)> + '_ {
+                        let mut elements = elements(&self.node)
+                            .filter(|x| T::can_cast(x.kind()) || Sep::can_cast(x.kind()))
+                            .peekable();
+
+                        std::iter::from_fn(move || loop {
+                            let Some(element) = T::cast(elements.next()?) else { continue };
+                            let separator = elements.next_if(|x| Sep::can_cast(x.kind())).and_then(Sep::cast);
+
+                            return Some((element, separator));
                         })
-                        .filter_map(T::cast)
+                    }
+
+                    pub fn has_trailing_separator(&self) -> bool {
+                        self.pairs().last().is_some_and(|(_, separator)| separator.is_some())
+                    }
+
+                    pub fn is_empty(&self) -> bool { self.elements().next().is_none() }
                 }
 
-        "#}.to_owned(),
-		);
+        "#},
+		}
 	}
 }
@@ -0,0 +1,256 @@
+// Crate Uses
+use super::{
+	gen::{separated_list, Cardinality, Enum, Field, Generator, NodeData, Struct},
+	utils::io::{GeneratedFile, Uses},
+};
+
+// External Uses
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use ungrammar::Rule;
+use ungrammar_extra::TokenCategory;
+
+
+
+impl Generator<'_> {
+	/// Generates the `ToSource` impls, which print every node by walking its rule in grammar order,
+	/// tokens missing from the tree are printed with their literal when it's their text (keywords
+	/// and punctuation), the others are left out. Hand-written nodes are printed through their own
+	/// `ToSource` impl
+	pub(crate) fn gen_printer(&self, nodes: &[NodeData]) -> GeneratedFile {
+		let text = self.options.backend.raw_token_text();
+
+		let tokens = self.token_kinds().map(|kind| {
+			let ty = format_ident!("{}", kind.name);
+
+			quote! {
+				impl ToSource for #ty {
+					fn print(&self, printer: &mut Printer) {
+						let Some(token) = self.clone().inner().into_token() else { return };
+						if let Some(text) = text(&token) {
+							printer.token(token.kind(), text)
+						}
+					}
+				}
+			}
+		});
+
+		let nodes = nodes.iter().map(|node| match node {
+			NodeData::Struct(s) => print_struct(self, s),
+			NodeData::Enum(e) => print_enum(e),
+		});
+
+		let content = quote! {
+			/// What goes between two printed tokens
+			#[derive(Debug, Clone, Copy, Default)]
+			pub enum Spacing {
+				/// Tokens are printed right next to each other
+				Compact,
+				/// A single space between every two tokens
+				#[default]
+				Spaced,
+				/// A single space between the tokens the function returns `true` for
+				Custom(fn(SyntaxKind, SyntaxKind) -> bool),
+			}
+
+			/// Collects printed tokens into text, following the spacing policy
+			pub struct Printer {
+				spacing: Spacing,
+				last: Option<SyntaxKind>,
+				out: String,
+			}
+
+			impl Printer {
+				pub fn new(spacing: Spacing) -> Self { Self { spacing, last: None, out: String::new() } }
+
+				pub fn token(&mut self, kind: SyntaxKind, text: &str) {
+					if let Some(last) = self.last {
+						let space = match self.spacing {
+							Spacing::Compact => false,
+							Spacing::Spaced => true,
+							Spacing::Custom(space) => space(last, kind),
+						};
+						if space { self.out.push(' ') }
+					}
+
+					self.out.push_str(text);
+					self.last = Some(kind);
+				}
+
+				pub fn finish(self) -> String { self.out }
+			}
+
+			/// Turns a node, or a token, back into source text, the tokens of a cstree tree
+			/// built without a resolver have no text to print
+			pub trait ToSource {
+				fn print(&self, printer: &mut Printer);
+
+				fn to_source(&self) -> String { self.to_source_with(Spacing::default()) }
+
+				fn to_source_with(&self, spacing: Spacing) -> String {
+					let mut printer = Printer::new(spacing);
+					self.print(&mut printer);
+					printer.finish()
+				}
+			}
+
+			#text
+
+			#(#tokens)*
+
+			#(#nodes)*
+		};
+
+		GeneratedFile {
+			name: "printer.rs",
+			uses: Some(Uses {
+				mods: vec![],
+				std: vec![],
+				krate: vec!["super::{*, ast::*, token::*}".to_owned()],
+				external: vec![],
+			}),
+			content: content.to_string(),
+		}
+	}
+}
+
+fn print_struct(generator: &Generator, s: &Struct) -> TokenStream {
+	let ty = format_ident!("{}", s.name);
+
+	// Fields are lowered in the same order the rule is walked, so each field is taken as its rule is reached
+	let mut walk = RuleWalk { generator, fields: s.fields.iter() };
+	let body = walk.rule(&generator.grammar[s.node].rule);
+
+	quote! {
+		impl ToSource for #ty {
+			fn print(&self, printer: &mut Printer) {
+				#body
+			}
+		}
+	}
+}
+
+fn print_enum(e: &Enum) -> TokenStream {
+	let ty = format_ident!("{}", e.name);
+
+	let variants = e.token_variants
+		.iter()
+		.map(String::as_str)
+		.chain(e.node_variants.iter().map(|v| v.name.as_str()))
+		.map(|name| format_ident!("{}", name));
+
+	quote! {
+		impl ToSource for #ty {
+			fn print(&self, printer: &mut Printer) {
+				match self {
+					#(Self::#variants(x) => x.print(printer),)*
+				}
+			}
+		}
+	}
+}
+
+struct RuleWalk<'a> {
+	generator: &'a Generator<'a>,
+	fields: std::slice::Iter<'a, Field>,
+}
+
+impl RuleWalk<'_> {
+	fn rule(&mut self, rule: &Rule) -> TokenStream {
+		match rule {
+			Rule::Labeled { rule, .. } | Rule::Opt(rule) => self.rule(rule),
+			Rule::Node(_) | Rule::Token(_) | Rule::Alt(_) => self.field(rule),
+			Rule::Seq(rules) => {
+				let mut printed = vec![];
+				let mut rest = rules.as_slice();
+				while let Some(first) = rest.first() {
+					let len = match separated_list(rest) {
+						Some((_, separator, len)) => {
+							printed.push(self.separated(separator));
+							len
+						},
+						None => {
+							printed.push(self.rule(first));
+							1
+						},
+					};
+					rest = &rest[len..];
+				}
+
+				printed.into_iter().collect()
+			},
+			Rule::Rep(rule) => self.field(rule),
+		}
+	}
+
+	fn separated(&mut self, separator: &Rule) -> TokenStream {
+		let Some(Field::Separated { name, .. }) = self.fields.next() else { return quote!() };
+		let fill = self.fill(separator);
+		let name = format_ident!("{}", name);
+
+		// Separators missing from between the elements are filled in, a trailing one is kept as is
+		quote! {
+			let list = self.#name();
+			let mut pairs = list.pairs().peekable();
+			while let Some((x, separator)) = pairs.next() {
+				x.print(printer);
+				match separator {
+					Some(separator) => separator.print(printer),
+					None if pairs.peek().is_some() => { #fill }
+					None => {},
+				}
+			}
+		}
+	}
+
+	fn field(&mut self, rule: &Rule) -> TokenStream {
+		let Some(field) = self.fields.next() else { return quote!() };
+
+		match field {
+			// Printed as their rule is reached, since the rule has the separator
+			Field::Separated { .. } => quote!(),
+			Field::Group { name, items } => {
+				let name = format_ident!("{}", name);
+				let vars: Vec<_> = (0..items.len()).map(|i| format_ident!("x{}", i)).collect();
+
+				quote! { for (#(#vars),*) in self.#name() { #(#vars.print(printer);)* } }
+			},
+			Field::Node { name, cardinality: Cardinality::Many, .. }
+			| Field::Token { name, cardinality: Cardinality::Many, .. } => {
+				let name = format_ident!("{}", name);
+				quote! { for x in self.#name() { x.print(printer) } }
+			},
+			Field::Token { name, cardinality: Cardinality::One(_), optional: false, .. } => {
+				let name = format_ident!("{}", name);
+				let fill = self.fill(rule);
+
+				quote! {
+					match self.#name() {
+						Some(x) => x.print(printer),
+						None => { #fill }
+					}
+				}
+			},
+			Field::Node { name, .. } | Field::Token { name, .. } => {
+				let name = format_ident!("{}", name);
+				quote! { if let Some(x) = self.#name() { x.print(printer) } }
+			},
+		}
+	}
+
+	/// Prints a token rule that's missing from the tree, only when its literal is its text,
+	/// the literal of the others (ex: `'int'`) is a name and not something to print
+	fn fill(&self, rule: &Rule) -> TokenStream {
+		let Rule::Token(token) = rule else { return quote!() };
+		let literal = &self.generator.grammar[*token].name;
+		let Some(kind) = self.generator.token_kinds().find(|kind| &kind.literal == literal) else { return quote!() };
+
+		match kind.category {
+			Some(TokenCategory::Keyword | TokenCategory::Punct) => {
+				let kind = format_ident!("{}", kind.name);
+				quote! { printer.token(SyntaxKind::#kind, #literal) }
+			},
+			_ => quote!(),
+		}
+	}
+}
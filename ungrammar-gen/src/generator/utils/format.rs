@@ -1,5 +1,8 @@
 // Standard Uses
-use std::{path::Path, process::Command};
+use std::{io::Write, path::Path, process::{Command, Stdio}};
+
+// Crate Uses
+use crate::generator::error::GenError;
 
 // External Uses
 use ungrammar::{Grammar, Rule};
@@ -57,6 +60,28 @@ pub fn format_rule<'a>(rule: &'a Rule, grammar: &'a Grammar) -> impl std::fmt::D
 	Fmt { rule, grammar }
 }
 
-pub fn format(file: &Path) { Command::new("rustfmt").arg(file).status().unwrap(); }
+/// Formats the source through rustfmt's stdin, `file` is the path the source belongs to,
+/// rustfmt configuration is looked up from its directory when it exists
+pub fn format(file: &Path, source: &str) -> Result<String, GenError> {
+	let error = |reason: String| GenError::Format { path: file.to_owned(), reason };
+
+	let mut command = Command::new("rustfmt");
+	command.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+	if let Some(dir) = file.parent().filter(|dir| dir.is_dir()) {
+		command.current_dir(dir);
+	}
+
+	let mut child = command.spawn().map_err(|e| error(e.to_string()))?;
+	child.stdin.take().expect("stdin is piped")
+		.write_all(source.as_bytes())
+		.map_err(|e| error(e.to_string()))?;
 
+	let output = child.wait_with_output().map_err(|e| error(e.to_string()))?;
+	if !output.status.success() {
+		return Err(error(format!(
+			"rustfmt exited with {}: {}", output.status, String::from_utf8_lossy(&output.stderr).trim()
+		)))
+	}
 
+	String::from_utf8(output.stdout).map_err(|e| error(e.to_string()))
+}
@@ -1,39 +1,78 @@
 // Standard Uses
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
-// External Uses
-use eyre::Context;
+// Crate Uses
+use crate::generator::error::GenError;
 
 
 
-pub fn write_generated(out: &Path, file: &str, uses: Option<Uses>, content: String) {
-	let mut text = indoc::indoc! {r#"
+pub fn write_generated(out: &Path, name: &str, text: &str) -> Result<(), GenError> {
+	let path = out.join(name);
+
+	std::fs::write(&path, text).map_err(|source| GenError::Write { path, source })
+}
+
+/// Renders the file into its final formatted text, `out` is where the file would be
+/// written, so formatting picks up the same rustfmt configuration
+pub fn render_generated(out: &Path, file: GeneratedFile) -> Result<String, GenError> {
+	let path = out.join(file.name);
+
+	let text = indoc::indoc! {r#"
     	// This file is generated, do not edit
 		#![allow(clippy::all)]
 		
 		
-	"#}.to_string();
+	"#}.to_string() + &source(file);
+
+	super::format::format(&path, &text)
+}
+
+/// Renders `root` along with the modules it declares as a single formatted file, see [`bundle`]
+pub fn render_bundle(path: &Path, root: GeneratedFile, files: Vec<GeneratedFile>) -> Result<String, GenError> {
+	super::format::format(path, &bundle(root, files))
+}
+
+/// Source of `root` along with the modules it declares as a single file, that can be
+/// `include!`d since every module is inlined instead of being looked up as a file
+pub fn bundle(mut root: GeneratedFile, mut files: Vec<GeneratedFile>) -> String {
+	let mods = root.uses.as_mut().map(|uses| std::mem::take(&mut uses.mods)).unwrap_or_default();
+
+	let mut text = "// This file is generated, do not edit\n\n".to_owned() + &source(root);
+	for r#mod in mods {
+		let Some(index) = files.iter().position(|f| f.name.strip_suffix(".rs") == Some(&r#mod)) else { continue };
+		let file = files.remove(index);
+
+		text += &*format!("\n#[allow(clippy::all)]\npub mod {} {{\n{}}}\n", r#mod, source(file));
+	}
+
+	text
+}
+
+/// Unformatted source of the file, without the generated file header
+fn source(file: GeneratedFile) -> String {
+	let GeneratedFile { uses, content, .. } = file;
+	let mut text = String::new();
 
 	if let Some(uses) = uses {
-		if uses.mods.len() > 0 {
+		if !uses.mods.is_empty() {
 			text += "// Relative Modules\n";
 			for r#mod in uses.mods { text += &*format!("pub mod {};\n", r#mod) }
 			text += "\n";
 		}
 
-		if uses.std.len() > 0 {
+		if !uses.std.is_empty() {
 			//text += "// Standard Uses\n";
 			for std in uses.std { text += &*format!("use {std};\n"); }
 			text += "\n";
 		}
 
-		if uses.krate.len() > 0 {
+		if !uses.krate.is_empty() {
 			//text += "// Crate Uses\n";
 			for krate in uses.krate { text += &*format!("use {krate};\n"); }
 			text += "\n";
 		}
 
-		if uses.external.len() > 0 {
+		if !uses.external.is_empty() {
 			//text += "// External Uses\n";
 			for external in uses.external { text += &*format!("use {external};\n"); }
 			text += "\n";			
@@ -42,22 +81,15 @@ pub fn write_generated(out: &Path, file: &str, uses: Option<Uses>, content: Stri
 	
 	text += &*format!("\n\n{content}\n");
 
-	write(out.join(file), text);
+	text
 }
 
-fn write(file: PathBuf, contents: String) {
-	if file.file_name().is_none() {
-		panic!("Given path has to be a file path.\nPath: {}", file.display());
-	}
-
-	std::fs::write(&file, contents)
-		.with_context(|| format!("Could not write into file at: \n{}", file.display()))
-		.unwrap();
-
-	super::format::format(&file);
+pub struct GeneratedFile {
+	pub name: &'static str,
+	pub uses: Option<Uses>,
+	pub content: String,
 }
 
-
 pub struct Uses {
 	pub mods: Vec<String>,
 	pub std: Vec<String>,
@@ -28,3 +28,13 @@ pub fn to_snake_case(x: &str) -> String {
 
 	s
 }
+
+/// Names a type after a label (ex: `else_branch` to `ElseBranch`)
+pub fn to_pascal_case(x: &str) -> String {
+	x.split('_')
+		.filter_map(|word| {
+			let mut chars = word.chars();
+			chars.next().map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+		})
+		.collect()
+}
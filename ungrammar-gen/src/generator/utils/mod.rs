@@ -2,3 +2,5 @@
 pub mod lint;
 pub mod format;
 pub mod io;
+pub mod source;
+pub mod suggest;
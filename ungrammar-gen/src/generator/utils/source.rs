@@ -0,0 +1,105 @@
+// Standard Uses
+use std::{iter::Peekable, str::Chars};
+
+// Crate Uses
+use crate::generator::error::Position;
+
+
+
+/// Source of the grammar, lexed once to find where things are used in it.
+///
+/// Ungrammar doesn't keep positions around, so this scans the grammar source again
+pub struct GrammarSource {
+	items: Vec<(Item, Position)>,
+}
+
+impl GrammarSource {
+	pub fn new(source: &str) -> Self {
+		Self { items: lex(source) }
+	}
+
+	/// Finds where a token literal is used in the definition of a node, `occurrence` picks
+	/// which use when the node uses the literal more than once
+	pub fn locate_token(&self, node: &str, literal: &str, occurrence: usize) -> Option<Position> {
+		let items = &self.items;
+
+		let is_definition = |i: usize| matches!(
+			(&items[i].0, items.get(i + 1)), (Item::Ident(_), Some((Item::Eq, _)))
+		);
+
+		let start = (0..items.len()).find(|&i| is_definition(i) && items[i].0 == Item::Ident(node.to_owned()))?;
+		let end = (start + 1..items.len()).find(|&i| is_definition(i)).unwrap_or(items.len());
+
+		items[start..end]
+			.iter()
+			.filter(|(item, _)| *item == Item::Token(literal.to_owned()))
+			.nth(occurrence)
+			.map(|(_, position)| *position)
+	}
+}
+
+#[derive(PartialEq)]
+enum Item {
+	Ident(String),
+	Token(String),
+	Eq,
+	Other,
+}
+
+fn lex(source: &str) -> Vec<(Item, Position)> {
+	let mut items = vec![];
+	let mut chars = source.chars().peekable();
+	let mut position = Position { line: 1, column: 1 };
+
+	while let Some(&c) = chars.peek() {
+		let start = position;
+
+		match c {
+			'/' => {
+				advance(&mut chars, &mut position);
+				if chars.peek() == Some(&'/') {
+					while chars.peek().is_some_and(|c| *c != '\n') { advance(&mut chars, &mut position); }
+				}
+			},
+			'\'' => {
+				advance(&mut chars, &mut position);
+				let mut literal = String::new();
+				while let Some(c) = advance(&mut chars, &mut position) {
+					match c {
+						'\\' => literal.extend(advance(&mut chars, &mut position)),
+						'\'' => break,
+						c => literal.push(c),
+					}
+				}
+				items.push((Item::Token(literal), start));
+			},
+			c if c.is_alphanumeric() || c == '_' => {
+				let mut ident = String::new();
+				while chars.peek().is_some_and(|c| c.is_alphanumeric() || *c == '_') {
+					ident.extend(advance(&mut chars, &mut position));
+				}
+				items.push((Item::Ident(ident), start));
+			},
+			'=' => {
+				advance(&mut chars, &mut position);
+				items.push((Item::Eq, start));
+			},
+			c if c.is_whitespace() => { advance(&mut chars, &mut position); },
+			_ => {
+				advance(&mut chars, &mut position);
+				items.push((Item::Other, start));
+			},
+		}
+	}
+
+	items
+}
+
+fn advance(chars: &mut Peekable<Chars>, position: &mut Position) -> Option<char> {
+	let c = chars.next()?;
+	match c {
+		'\n' => *position = Position { line: position.line + 1, column: 1 },
+		_ => position.column += 1,
+	}
+	Some(c)
+}
@@ -0,0 +1,42 @@
+
+/// Candidates close enough to `target` to likely be what was meant, closest first
+pub fn similar<'a>(target: &str, candidates: impl Iterator<Item = &'a str>) -> Vec<String> {
+	const MAX_SUGGESTIONS: usize = 3;
+	let threshold = (target.chars().count() / 3).max(1);
+
+	let mut similar: Vec<_> = candidates
+		.filter_map(|candidate| {
+			let distance = match candidate.eq_ignore_ascii_case(target) {
+				true => 0,
+				false => edit_distance(target, candidate),
+			};
+			(distance <= threshold).then_some((distance, candidate))
+		})
+		.collect();
+
+	similar.sort();
+	similar.into_iter().take(MAX_SUGGESTIONS).map(|(_, candidate)| candidate.to_owned()).collect()
+}
+
+/// Edit distance between both strings, where swapping two adjacent characters counts as
+/// a single edit since it's such a common typo (optimal string alignment distance)
+fn edit_distance(a: &str, b: &str) -> usize {
+	let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+	let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+	for (i, row) in d.iter_mut().enumerate() { row[0] = i; }
+	for (j, cell) in d[0].iter_mut().enumerate() { *cell = j; }
+
+	for i in 1..=a.len() {
+		for j in 1..=b.len() {
+			let cost = usize::from(a[i - 1] != b[j - 1]);
+			d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+
+			if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+				d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+			}
+		}
+	}
+
+	d[a.len()][b.len()]
+}
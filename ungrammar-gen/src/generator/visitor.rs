@@ -0,0 +1,149 @@
+// Standard Uses
+use std::collections::HashSet;
+
+// Crate Uses
+use super::{
+	gen::{Cardinality, Enum, Field, Generator, NodeData, Struct},
+	utils::{io::{GeneratedFile, Uses}, lint::to_snake_case},
+};
+
+// External Uses
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+
+
+
+impl Generator<'_> {
+	/// Generates the `Visitor` trait, with a `visit_<node>` and `leave_<node>` method for every node,
+	/// and the `Walker` impls that call them while recursing through the node fields
+	pub(crate) fn gen_visitor(&self, nodes: &[NodeData]) -> GeneratedFile {
+		let names: HashSet<&str> = nodes.iter().map(node_name).collect();
+
+		let methods = nodes.iter().map(|node| {
+			let ty = format_ident!("{}", node_name(node));
+			let (visit, leave) = method_names(node_name(node));
+
+			quote! {
+				fn #visit(&mut self, node: &#ty) {}
+				fn #leave(&mut self, node: &#ty) {}
+			}
+		});
+
+		let walkers = nodes.iter().map(|node| match node {
+			NodeData::Struct(s) => walk_struct(s, &names),
+			NodeData::Enum(e) => walk_enum(e, &names),
+		});
+
+		let content = quote! {
+			/// Called for every node while walking the tree, before (`visit_*`) and after (`leave_*`)
+			/// walking its fields, methods do nothing by default
+			#[allow(unused_variables)]
+			pub trait Visitor {
+				#(#methods)*
+			}
+
+			/// Walks the node and everything under it, see [`Visitor`]
+			pub trait Walker {
+				fn walk<V: Visitor + ?Sized>(&self, visitor: &mut V);
+			}
+
+			pub fn walk<N: Walker, V: Visitor + ?Sized>(node: &N, visitor: &mut V) { node.walk(visitor) }
+
+			#(#walkers)*
+		};
+
+		GeneratedFile {
+			name: "visitor.rs",
+			uses: Some(Uses {
+				mods: vec![],
+				std: vec![],
+				krate: vec!["super::ast::*".to_owned()],
+				external: vec![],
+			}),
+			content: content.to_string(),
+		}
+	}
+}
+
+fn walk_struct(s: &Struct, names: &HashSet<&str>) -> TokenStream {
+	let ty = format_ident!("{}", s.name);
+	let (visit, leave) = method_names(&s.name);
+
+	// Only nodes are walked, and only the ones that are generated (ex: not handwritten ones)
+	let fields = s.fields.iter().filter_map(|field| match field {
+		Field::Node { name, ty, cardinality, .. } if names.contains(ty.as_str()) => {
+			let name = format_ident!("{}", name);
+			Some(match cardinality {
+				Cardinality::One(_) => quote! { if let Some(x) = self.#name() { x.walk(visitor) } },
+				Cardinality::Many => quote! { for x in self.#name() { x.walk(visitor) } },
+			})
+		},
+		Field::Separated { name, ty, .. } if names.contains(ty.as_str()) => {
+			let name = format_ident!("{}", name);
+			Some(quote! { for x in self.#name().elements() { x.walk(visitor) } })
+		},
+		Field::Group { name, items } => {
+			let walked: Vec<_> = items.iter().map(|item| matches!(item, Field::Node { ty, .. } if names.contains(ty.as_str()))).collect();
+			if !walked.contains(&true) { return None }
+
+			let name = format_ident!("{}", name);
+			let vars: Vec<_> = walked.iter().enumerate().map(|(i, walked)| match walked {
+				true => format_ident!("x{}", i),
+				false => format_ident!("_"),
+			}).collect();
+			let walks = vars.iter().filter(|var| *var != "_");
+
+			Some(quote! { for (#(#vars),*) in self.#name() { #(#walks.walk(visitor);)* } })
+		},
+		_ => None,
+	});
+
+	quote! {
+		impl Walker for #ty {
+			fn walk<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+				visitor.#visit(self);
+				#(#fields)*
+				visitor.#leave(self);
+			}
+		}
+	}
+}
+
+fn walk_enum(e: &Enum, names: &HashSet<&str>) -> TokenStream {
+	let ty = format_ident!("{}", e.name);
+	let (visit, leave) = method_names(&e.name);
+
+	let variants: Vec<_> = e.node_variants
+		.iter()
+		.filter(|v| names.contains(v.name.as_str()))
+		.map(|v| format_ident!("{}", v.name))
+		.collect();
+
+	quote! {
+		impl Walker for #ty {
+			fn walk<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+				visitor.#visit(self);
+				#[allow(unreachable_patterns)]
+				match self {
+					#(Self::#variants(x) => x.walk(visitor),)*
+					_ => {},
+				}
+				visitor.#leave(self);
+			}
+		}
+	}
+}
+
+fn node_name(node: &NodeData) -> &str {
+	match node {
+		NodeData::Struct(s) => &s.name,
+		NodeData::Enum(e) => &e.name,
+	}
+}
+
+fn method_names(node: &str) -> (Ident, Ident) {
+	let name = to_snake_case(node);
+	let name = name.trim_end_matches('_');
+
+	(format_ident!("visit_{}", name), format_ident!("leave_{}", name))
+}
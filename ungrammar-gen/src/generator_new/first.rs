@@ -0,0 +1,126 @@
+// Standard Uses
+use std::collections::{BTreeSet, HashMap};
+
+// Crate Uses
+
+// External Uses
+use eyre::{bail, Result};
+use ungrammar::{Grammar, Node, Rule, Token};
+
+
+
+/// FIRST sets and nullability of every grammar node, which is what the parser
+/// looks at to decide which rule to take next
+pub struct FirstSets<'g> {
+	grammar: &'g Grammar,
+	kinds: &'g HashMap<Token, String>,
+	nullable: HashMap<Node, bool>,
+	first: HashMap<Node, BTreeSet<String>>,
+}
+
+impl<'g> FirstSets<'g> {
+	/// `kinds` are the kind names of the grammar tokens
+	pub fn new(grammar: &'g Grammar, kinds: &'g HashMap<Token, String>) -> Result<Self> {
+		let mut sets = Self {
+			grammar,
+			kinds,
+			nullable: grammar.iter().map(|node| (node, false)).collect(),
+			first: grammar.iter().map(|node| (node, BTreeSet::new())).collect(),
+		};
+
+		// Nodes refer to each other, so their sets grow until none of them changes
+		let mut changed = true;
+		while changed {
+			changed = false;
+
+			for node in grammar.iter() {
+				let rule = &grammar[node].rule;
+				let (nullable, first) = (sets.is_nullable(rule), sets.first(rule));
+
+				if nullable != sets.nullable[&node] || first != sets.first[&node] {
+					sets.nullable.insert(node, nullable);
+					sets.first.insert(node, first);
+					changed = true;
+				}
+			}
+		}
+
+		sets.check_left_recursion()?;
+		Ok(sets)
+	}
+
+	/// If the rule can match without consuming any token
+	pub fn is_nullable(&self, rule: &Rule) -> bool {
+		match rule {
+			Rule::Labeled { rule, .. } => self.is_nullable(rule),
+			Rule::Node(node) => self.nullable[node],
+			Rule::Token(_) => false,
+			Rule::Seq(rules) => self.is_nullable_seq(rules),
+			Rule::Alt(rules) => rules.iter().any(|rule| self.is_nullable(rule)),
+			Rule::Opt(_) | Rule::Rep(_) => true,
+		}
+	}
+
+	/// Kind names of the tokens the rule can start with
+	pub fn first(&self, rule: &Rule) -> BTreeSet<String> {
+		match rule {
+			Rule::Labeled { rule, .. } | Rule::Opt(rule) | Rule::Rep(rule) => self.first(rule),
+			Rule::Node(node) => self.first[node].clone(),
+			Rule::Token(token) => self.kinds.get(token).cloned().into_iter().collect(),
+			Rule::Seq(rules) => self.first_seq(rules),
+			Rule::Alt(rules) => rules.iter().flat_map(|rule| self.first(rule)).collect(),
+		}
+	}
+
+	pub fn is_nullable_seq(&self, rules: &[Rule]) -> bool { rules.iter().all(|rule| self.is_nullable(rule)) }
+
+	pub fn first_seq(&self, rules: &[Rule]) -> BTreeSet<String> {
+		let mut first = BTreeSet::new();
+		for rule in rules {
+			first.extend(self.first(rule));
+			if !self.is_nullable(rule) { break }
+		}
+		first
+	}
+
+	/// A node that can start with itself would make its parse function call itself forever
+	fn check_left_recursion(&self) -> Result<()> {
+		for node in self.grammar.iter() {
+			let mut seen = vec![];
+			let mut stack = self.left_nodes(&self.grammar[node].rule);
+
+			while let Some(next) = stack.pop() {
+				if next == node {
+					bail!(
+						"Node '{}' is left recursive, which can't be parsed by a recursive descent parser",
+						self.grammar[node].name
+					);
+				}
+				if seen.contains(&next) { continue }
+
+				seen.push(next);
+				stack.extend(self.left_nodes(&self.grammar[next].rule));
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Nodes the rule can start with, before consuming any token
+	fn left_nodes(&self, rule: &Rule) -> Vec<Node> {
+		match rule {
+			Rule::Labeled { rule, .. } | Rule::Opt(rule) | Rule::Rep(rule) => self.left_nodes(rule),
+			Rule::Node(node) => vec![*node],
+			Rule::Token(_) => vec![],
+			Rule::Seq(rules) => {
+				let mut nodes = vec![];
+				for rule in rules {
+					nodes.extend(self.left_nodes(rule));
+					if !self.is_nullable(rule) { break }
+				}
+				nodes
+			},
+			Rule::Alt(rules) => rules.iter().flat_map(|rule| self.left_nodes(rule)).collect(),
+		}
+	}
+}
@@ -1,18 +1,58 @@
 // Relative Modules
+mod first;
+mod parser;
 
 // Standard Uses
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 
 // Local Uses
+use crate::generator::{utils::io::{self, GeneratedFile, Uses}, KindsMeta};
+use crate::generator_new::{first::FirstSets, parser::ParserGen};
 
 // External Uses
-use eyre::Result;
+use eyre::{eyre, Result};
 use ungrammar::Grammar;
 use ungrammar_extra::KindsMetaInfo;
 
-#[allow(unused)]
+
+/// Generates a recursive descent parser for the grammar into `parser.rs` in `output_path`,
+/// it goes along with the code of [`crate::generator`], since it builds trees of its `SyntaxKind`
 pub fn from_path<M: KindsMetaInfo>(grammar_path: &Path, output_path: &Path) -> Result<()> {
+	from_path_with_meta(grammar_path, output_path, &KindsMeta::from_info::<M>())
+}
+
+/// Same as [`from_path`], but with the token kinds information given at runtime
+pub fn from_path_with_meta(grammar_path: &Path, output_path: &Path, meta: &KindsMeta) -> Result<()> {
 	let grammar: Grammar = std::fs::read_to_string(grammar_path)?.parse()?;
 
+	let text = io::render_generated(output_path, generate(&grammar, meta)?)?;
+	std::fs::write(output_path.join("parser.rs"), text)?;
+
 	Ok(())
 }
+
+/// Lowers the grammar into the parser file, the grammar must be parseable by looking only
+/// at the next token, when alternatives can start with the same token the first one is taken
+fn generate(grammar: &Grammar, meta: &KindsMeta) -> Result<GeneratedFile> {
+	let kinds = grammar
+		.tokens()
+		.map(|token| {
+			let literal = &grammar[token].name;
+			let kind = meta.get(literal).ok_or_else(|| eyre!("Token '{literal}' of the grammar has no kind"))?;
+			Ok((token, kind.name.clone()))
+		})
+		.collect::<Result<HashMap<_, _>>>()?;
+
+	let sets = FirstSets::new(grammar, &kinds)?;
+
+	Ok(GeneratedFile {
+		name: "parser.rs",
+		uses: Some(Uses {
+			mods: vec![],
+			std: vec![],
+			krate: vec!["super::kind::SyntaxKind".to_owned()],
+			external: vec![],
+		}),
+		content: ParserGen::new(grammar, &kinds, sets).generate().to_string(),
+	})
+}
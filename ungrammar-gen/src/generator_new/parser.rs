@@ -0,0 +1,178 @@
+// Standard Uses
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+// Crate Uses
+use crate::generator::utils::lint::to_snake_case;
+use crate::generator_new::first::FirstSets;
+
+// External Uses
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use ungrammar::{Grammar, Node, Rule, Token};
+
+
+
+/// Emits a recursive descent parser for the grammar, with a `parse_<node>` entry
+/// for every struct node that builds a tree in the shape the AST wrappers expect,
+/// the rules are written against the runtime of `ungrammar_extra::parser`
+pub struct ParserGen<'g> {
+	grammar: &'g Grammar,
+	kinds: &'g HashMap<Token, String>,
+	sets: FirstSets<'g>,
+}
+
+impl<'g> ParserGen<'g> {
+	pub fn new(grammar: &'g Grammar, kinds: &'g HashMap<Token, String>, sets: FirstSets<'g>) -> Self {
+		Self { grammar, kinds, sets }
+	}
+
+	pub fn generate(&self) -> TokenStream {
+		let referenced: HashSet<Node> = self.grammar.iter().flat_map(|node| nodes_of(&self.grammar[node].rule)).collect();
+
+		let functions = self.grammar.iter().map(|node| {
+			let name = function_name(&self.grammar[node].name);
+			let body = self.rule(&self.grammar[node].rule);
+
+			// Enum nodes only exist as part of others, so they might be parsed by nobody
+			let unused = (is_enum(&self.grammar[node].rule) && !referenced.contains(&node))
+				.then(|| quote! { #[allow(dead_code)] });
+
+			quote! {
+				#unused
+				fn #name(p: &mut Parser) {
+					#body
+				}
+			}
+		});
+
+		let entries = self.grammar.iter().filter(|node| !is_enum(&self.grammar[*node].rule)).map(|node| {
+			let kind = format_ident!("{}", self.grammar[node].name);
+			let name = function_name(&self.grammar[node].name);
+			let entry = format_ident!("parse_{}", name.to_string().trim_end_matches('_'));
+
+			quote! {
+				pub fn #entry(tokens: &[(SyntaxKind, &str)]) -> Parse {
+					runtime::root(tokens, SyntaxKind::#kind, #name)
+				}
+			}
+		});
+
+		quote! {
+			type Parser<'t> = ungrammar_extra::parser::Parser<'t, SyntaxKind>;
+			pub type Parse = ungrammar_extra::parser::Parse<SyntaxKind>;
+
+			/// Helpers the rules are parsed with, they're in a module of their own so they can't clash
+			/// with the functions of the grammar's nodes (ex: `node` of a `Node`)
+			mod runtime {
+				use super::*;
+
+				// Not every grammar has nodes inside others
+				#[allow(dead_code)]
+				pub(super) fn node(p: &mut Parser, kind: SyntaxKind, parse: fn(&mut Parser)) {
+					let marker = p.start();
+					parse(p);
+					marker.complete(p, kind);
+				}
+
+				/// Parses the whole input as the node, tokens left over are kept in it after an error
+				pub(super) fn root(tokens: &[(SyntaxKind, &str)], kind: SyntaxKind, parse: fn(&mut Parser)) -> Parse {
+					let mut p = Parser::new(tokens);
+					let root = p.start();
+					parse(&mut p);
+					if !p.at_end() {
+						p.error(&[]);
+						while !p.at_end() { p.bump() }
+					}
+					root.complete(&mut p, kind);
+
+					p.finish()
+				}
+			}
+
+			#(#entries)*
+
+			#(#functions)*
+		}
+	}
+
+	fn rule(&self, rule: &Rule) -> TokenStream {
+		match rule {
+			Rule::Labeled { rule, .. } => self.rule(rule),
+			Rule::Node(node) => {
+				let name = function_name(&self.grammar[*node].name);
+				match is_enum(&self.grammar[*node].rule) {
+					true => quote! { #name(p); },
+					false => {
+						let kind = format_ident!("{}", self.grammar[*node].name);
+						quote! { runtime::node(p, SyntaxKind::#kind, #name); }
+					},
+				}
+			},
+			Rule::Token(token) => {
+				let kind = format_ident!("{}", self.kinds[token]);
+				quote! { p.expect(SyntaxKind::#kind); }
+			},
+			Rule::Seq(rules) => rules.iter().map(|rule| self.rule(rule)).collect(),
+			Rule::Opt(rule) => {
+				let (first, body) = (self.first(rule), self.rule(rule));
+				quote! { if p.at_any(&[#(SyntaxKind::#first),*]) { #body } }
+			},
+			Rule::Rep(rule) => {
+				let (first, body) = (self.first(rule), self.rule(rule));
+
+				// Separated lists (ex: `(',' T)* ','?`) need to see past the separator to tell another
+				// element from a trailing separator
+				if let Rule::Seq(rules) = &**rule {
+					if let [Rule::Token(_), rest @ ..] = rules.as_slice() {
+						if !self.sets.is_nullable_seq(rest) {
+							let next = idents(self.sets.first_seq(rest));
+							return quote! {
+								while p.at_any(&[#(SyntaxKind::#first),*]) && p.nth(1).is_some_and(|kind| [#(SyntaxKind::#next),*].contains(&kind)) {
+									#body
+								}
+							};
+						}
+					}
+				}
+
+				quote! { while p.at_any(&[#(SyntaxKind::#first),*]) { #body } }
+			},
+			Rule::Alt(rules) => {
+				// The first alternative that can start with the current token is taken, when none can
+				// it's either the one that can be empty, or an error
+				let branches = rules.iter().filter(|rule| !self.sets.is_nullable(rule)).map(|rule| {
+					let (first, body) = (self.first(rule), self.rule(rule));
+					quote! { if p.at_any(&[#(SyntaxKind::#first),*]) { #body } else }
+				});
+
+				let fallback = match rules.iter().find(|rule| self.sets.is_nullable(rule)) {
+					Some(rule) => self.rule(rule),
+					None => {
+						let first = self.first(rule);
+						quote! { p.error(&[#(SyntaxKind::#first),*]); }
+					},
+				};
+
+				quote! { #(#branches)* { #fallback } }
+			},
+		}
+	}
+
+	fn first(&self, rule: &Rule) -> Vec<Ident> { idents(self.sets.first(rule)) }
+}
+
+fn idents(kinds: BTreeSet<String>) -> Vec<Ident> { kinds.iter().map(|kind| format_ident!("{}", kind)).collect() }
+
+/// Nodes that are parsed through their own rule instead of starting a node of their own, like the AST enums
+fn is_enum(rule: &Rule) -> bool { matches!(rule, Rule::Alt(_)) }
+
+fn function_name(node: &str) -> Ident { format_ident!("{}", to_snake_case(node)) }
+
+fn nodes_of(rule: &Rule) -> Vec<Node> {
+	match rule {
+		Rule::Labeled { rule, .. } | Rule::Opt(rule) | Rule::Rep(rule) => nodes_of(rule),
+		Rule::Node(node) => vec![*node],
+		Rule::Token(_) => vec![],
+		Rule::Seq(rules) | Rule::Alt(rules) => rules.iter().flat_map(nodes_of).collect(),
+	}
+}
@@ -3,5 +3,6 @@
 // Relative Modules
 // pub mod codegen;
 // pub mod parser;
+pub mod build;
 pub mod generator;
 pub mod generator_new;
@@ -1,7 +1,131 @@
-// Relative Modules
-// pub mod codegen;
-// pub mod parser;
+// Standard Uses
+use std::{collections::BTreeMap, path::PathBuf};
 
-fn main() {
-	println!("Hello, world!");
+// External Uses
+use clap::{Args, Parser, Subcommand};
+use eyre::{bail, Context, Result};
+use ungrammar_gen::generator::{self, Backend, CustomType, GeneratorOptions, HandwrittenNode, KindsMeta};
+
+
+
+#[derive(Parser)]
+#[command(version, about = "Generates typed syntax tree code from ungrammar files")]
+struct Cli {
+	#[command(subcommand)]
+	command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+	/// Generate the AST code of a grammar into a directory
+	Generate(Generate),
+}
+
+#[derive(Args)]
+struct Generate {
+	/// The `.ungram` grammar file
+	grammar: PathBuf,
+
+	/// Directory the generated code is written into
+	#[arg(short, long)]
+	out: PathBuf,
+
+	/// Toml file with the token kinds information (literal, kind name and description of each token)
+	#[arg(short, long)]
+	kinds: PathBuf,
+
+	/// Path of the token kind enum used by the generated code, overrides `token_kind` in the kinds file
+	#[arg(long)]
+	token_kind: Option<String>,
+
+	/// Syntax tree library the generated code is built on, either `cstree` or `rowan`
+	#[arg(long, default_value = "cstree")]
+	backend: Backend,
+
+	/// Span type of the generated API and the `fn(TextRange) -> Span` that converts into it
+	#[arg(long, num_args = 2, value_names = ["TYPE", "FN"])]
+	span: Option<Vec<String>>,
+
+	/// Text type of the generated API and the `fn(&SyntaxToken) -> Text` that converts into it
+	#[arg(long, num_args = 2, value_names = ["TYPE", "FN"])]
+	text: Option<Vec<String>>,
+
+	/// Grammar node that's written by hand, so it's left out of the generated code, can be repeated
+	#[arg(long, value_name = "NODE")]
+	handwritten: Vec<String>,
+
+	/// Fail if the code in the output directory is not up to date with the grammar, without writing anything
+	#[arg(long, group = "mode")]
+	check: bool,
+
+	/// Report which files would be written, without writing anything
+	#[arg(long, group = "mode")]
+	dry_run: bool,
+
+	/// Print the generated code to stdout instead of writing it
+	#[arg(long, group = "mode")]
+	stdout: bool,
+}
+
+fn main() -> Result<()> {
+	match Cli::parse().command {
+		Command::Generate(args) => generate(args),
+	}
+}
+
+fn generate(args: Generate) -> Result<()> {
+	let meta = KindsMeta::from_path(&args.kinds)?;
+	let Some(token_kind) = args.token_kind.as_deref().or(meta.token_kind.as_deref()) else {
+		bail!("No token kind path given, either pass `--token-kind` or set `token_kind` in the kinds file")
+	};
+
+	let options = GeneratorOptions {
+		backend: args.backend,
+		span: args.span.as_deref().map(custom_type),
+		text: args.text.as_deref().map(custom_type),
+		handwritten: args.handwritten.iter().map(|node| HandwrittenNode { node: node.clone(), replacement: None }).collect(),
+		node_hooks: vec![],
+	};
+
+	if args.check {
+		return Ok(generator::check_with_meta(&args.grammar, &args.out, &meta, token_kind, &options)?);
+	}
+
+	if !(args.dry_run || args.stdout) {
+		return Ok(generator::from_path_with_meta(&args.grammar, &args.out, &meta, token_kind, &options)?);
+	}
+
+	let files = generator::to_memory_with_meta(&args.grammar, &args.out, &meta, token_kind, &options)?;
+	report(&args, files)
+}
+
+fn custom_type(values: &[String]) -> CustomType {
+	CustomType { path: values[0].clone(), convert: values[1].clone() }
+}
+
+fn report(args: &Generate, files: BTreeMap<String, String>) -> Result<()> {
+	for (file, generated) in files {
+		let target = args.out.join(&file);
+
+		if args.stdout {
+			println!("// {file}\n{generated}");
+			continue;
+		}
+
+		let existing = match target.exists() {
+			true => Some(
+				std::fs::read_to_string(&target)
+					.with_context(|| format!("Couldn't read file at path '{}'", target.display()))?
+			),
+			false => None,
+		};
+
+		match existing {
+			Some(existing) if existing == generated => println!("unchanged {}", target.display()),
+			Some(_) => println!("update    {}", target.display()),
+			None => println!("create    {}", target.display()),
+		}
+	}
+
+	Ok(())
 }